@@ -1,2 +1,10 @@
 mod client;
-pub use self::client::{ApiClient,ClientOptions,HeadersOptions};
+mod paging;
+mod quorum;
+mod single_flight;
+mod transport;
+pub use self::client::{ApiClient,ApiClientBuilder};
+pub use crate::options::{ClientOptions,HeadersOptions};
+pub use self::paging::{BlockTransactions, BlocksRange, TxHistory};
+pub use self::quorum::QuorumClient;
+pub use self::transport::{EsploraTransport,TransportFuture};
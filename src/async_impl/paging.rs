@@ -0,0 +1,271 @@
+use std::fmt;
+use std::ops::RangeBounds;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{ready, Stream, StreamExt, TryStreamExt};
+
+use crate::blocks::{check_txs_page, BlockRange, Step, BLOCK_TXS_PAGE_SIZE};
+use crate::data::blockstream::{BlockFormat, TransactionFormat};
+use crate::endpoint;
+use crate::error::Error;
+use crate::history::History;
+use super::ApiClient;
+
+/// Stream over the full transaction history of an address or scripthash,
+/// returned by [ApiClient::address_history](ApiClient::address_history) and
+/// [ApiClient::script_hash_history](ApiClient::script_hash_history).
+///
+/// Yields mempool transactions first, then confirmed transactions newest first, fetching pages as it is polled.
+/// The stream ends after the first error.
+pub struct TxHistory {
+    client: ApiClient,
+    history: History,
+    page: Option<BoxFuture<'static, Result<Vec<TransactionFormat>, Error>>>,
+}
+
+impl fmt::Debug for TxHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxHistory").field("history", &self.history).finish()
+    }
+}
+
+impl TxHistory {
+    pub(crate) fn new(client: ApiClient, history: History) -> Self {
+        TxHistory {
+            client,
+            history,
+            page: None,
+        }
+    }
+    /// Only walk confirmed transactions.
+    pub fn skip_mempool(mut self) -> Self {
+        self.history.skip_mempool();
+        self
+    }
+    /// End the stream at txid, which is not yielded, e.g. the newest transaction already known.
+    pub fn stop_at_txid(mut self, txid: impl Into<String>) -> Self {
+        self.history.stop_at_txid(txid.into());
+        self
+    }
+    /// End the stream at the first transaction confirmed below height.
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.history.min_height(height);
+        self
+    }
+}
+
+impl Stream for TxHistory {
+    type Item = Result<TransactionFormat, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(page) = &mut this.page {
+                let page = ready!(page.as_mut().poll(cx));
+                this.page = None;
+                match page {
+                    Ok(page) => this.history.push_page(page),
+                    Err(err) => {
+                        this.history.finish();
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+            }
+            if let Some(tx) = this.history.pop() {
+                return Poll::Ready(Some(Ok(tx)));
+            }
+            let endpoint = match this.history.next_page() {
+                Some(endpoint) => endpoint,
+                None => return Poll::Ready(None),
+            };
+            let client = this.client.clone();
+            this.page = Some(Box::pin(async move { client.call(endpoint).await }));
+        }
+    }
+}
+
+/// Stream over every transaction of a block, in block order, returned by
+/// [ApiClient::block_transactions](ApiClient::block_transactions).
+///
+/// Fetches the block to learn its `tx_count`, then its pages of 25 transactions, checking each page is complete.
+/// The stream ends after the first error.
+pub struct BlockTransactions {
+    client: ApiClient,
+    hash: String,
+    concurrency: usize,
+    stream: Option<BoxStream<'static, Result<TransactionFormat, Error>>>,
+    done: bool,
+}
+
+impl fmt::Debug for BlockTransactions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockTransactions")
+            .field("hash", &self.hash)
+            .field("concurrency", &self.concurrency)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl BlockTransactions {
+    pub(crate) fn new(client: ApiClient, hash: &str) -> Self {
+        BlockTransactions {
+            client,
+            hash: hash.to_string(),
+            concurrency: 1,
+            stream: None,
+            done: false,
+        }
+    }
+    /// Fetch up to concurrency pages at once, 1 by default. Transactions are still yielded in block order.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+/// Pages of the block transactions, fetched concurrency at a time and flattened in order.
+fn block_txs_pages(
+    client: ApiClient,
+    hash: String,
+    concurrency: usize,
+) -> BoxStream<'static, Result<TransactionFormat, Error>> {
+    let block = {
+        let (client, hash) = (client.clone(), hash.clone());
+        async move { client.call(endpoint::block(&hash)).await }
+    };
+    stream::once(block)
+        .map_ok(move |block| {
+            let (client, hash, tx_count) = (client.clone(), hash.clone(), block.tx_count);
+            stream::iter((0..tx_count).step_by(BLOCK_TXS_PAGE_SIZE as usize))
+                .map(move |start| {
+                    let (client, hash) = (client.clone(), hash.clone());
+                    async move {
+                        let page = client.call(endpoint::block_txs(&hash, Some(start as i32))).await?;
+                        check_txs_page(&hash, tx_count, start, page)
+                    }
+                })
+                .buffered(concurrency)
+                .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+                .try_flatten()
+        })
+        .try_flatten()
+        .boxed()
+}
+
+impl Stream for BlockTransactions {
+    type Item = Result<TransactionFormat, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let (client, hash, concurrency) = (&this.client, &this.hash, this.concurrency);
+        let stream = this
+            .stream
+            .get_or_insert_with(|| block_txs_pages(client.clone(), hash.clone(), concurrency));
+        let item = ready!(stream.poll_next_unpin(cx));
+        if !matches!(item, Some(Ok(_))) {
+            this.done = true;
+            this.stream = None;
+        }
+        Poll::Ready(item)
+    }
+}
+
+/// Stream over a range of blocks in ascending height order, returned by
+/// [ApiClient::blocks_range](ApiClient::blocks_range).
+///
+/// Blocks are fetched 10 at a time and must each link to the previous one through `previousblockhash`,
+/// a broken link (e.g. a reorg while walking) ends the stream with `Error::Inconsistent`
+/// after checking the status of the previous block, which drops it from the client caches if it left the best chain.
+/// Heights above the tip are awaited, so an unbounded range follows the tip indefinitely.
+/// The stream ends after the first error.
+pub struct BlocksRange {
+    client: ApiClient,
+    range: BlockRange,
+    pending: Option<BoxFuture<'static, Result<Fetched, Error>>>,
+}
+
+/// Outcome of the request of a `Step`.
+enum Fetched {
+    Tip(i32),
+    Page(Vec<BlockFormat>),
+    Waited,
+}
+
+impl fmt::Debug for BlocksRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlocksRange").field("range", &self.range).finish()
+    }
+}
+
+impl BlocksRange {
+    pub(crate) fn new(client: ApiClient, range: impl RangeBounds<u32>) -> Self {
+        BlocksRange {
+            client,
+            range: BlockRange::new(range),
+            pending: None,
+        }
+    }
+    /// Time between two checks of the tip while waiting for the next block, 30s by default.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.range.poll_interval(poll_interval);
+        self
+    }
+}
+
+impl Stream for BlocksRange {
+    type Item = Result<BlockFormat, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(pending) = &mut this.pending {
+                let fetched = ready!(pending.as_mut().poll(cx));
+                this.pending = None;
+                let pushed = match fetched {
+                    Ok(Fetched::Tip(height)) => {
+                        this.range.push_tip(height);
+                        Ok(())
+                    }
+                    Ok(Fetched::Page(page)) => this.range.push_page(page),
+                    Ok(Fetched::Waited) => Ok(()),
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = pushed {
+                    this.range.finish();
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+            let client = this.client.clone();
+            this.pending = match this.range.step() {
+                Ok(Step::Block(block)) => return Poll::Ready(Some(Ok(block))),
+                Ok(Step::Done) => return Poll::Ready(None),
+                Ok(Step::Tip) => Some(Box::pin(async move {
+                    client.call(endpoint::blocks_tip_height()).await.map(Fetched::Tip)
+                })),
+                Ok(Step::Page(endpoint)) => Some(Box::pin(async move {
+                    client.call(endpoint).await.map(Fetched::Page)
+                })),
+                Ok(Step::Wait(delay)) => Some(Box::pin(async move {
+                    tokio::time::delay_for(delay).await;
+                    Ok(Fetched::Waited)
+                })),
+                Ok(Step::Unlinked(status, err)) => Some(Box::pin(async move {
+                    let _ = client.call(status).await;
+                    Err(err)
+                })),
+                Err(err) => {
+                    this.range.finish();
+                    return Poll::Ready(Some(Err(err)));
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+use crate::error::Error;
+use crate::transport::{Request, Response};
+
+type Flight = Shared<BoxFuture<'static, Result<Response, Arc<Error>>>>;
+
+/// Requests in flight, shared by the clones of a client de-duplicating identical concurrent requests.
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    flights: Mutex<HashMap<Request, Flight>>,
+}
+
+impl SingleFlight {
+    /// Join the flight of an identical request if any, otherwise start one with send.
+    pub(crate) async fn run<F, Fut>(self: &Arc<Self>, request: &Request, send: F) -> Result<Response, Error>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Response, Error>> + Send + 'static,
+    {
+        let flight = {
+            let mut flights = self.lock();
+            match flights.get(request) {
+                Some(flight) => flight.clone(),
+                None => {
+                    let (single_flight, key, send) = (self.clone(), request.clone(), send());
+                    let flight = async move {
+                        let outcome = send.await.map_err(Arc::new);
+                        single_flight.lock().remove(&key);
+                        outcome
+                    }
+                    .boxed()
+                    .shared();
+                    flights.insert(request.clone(), flight.clone());
+                    flight
+                }
+            }
+        };
+        flight.await.map_err(shared_error)
+    }
+    fn lock(&self) -> MutexGuard<'_, HashMap<Request, Flight>> {
+        self.flights.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Error of a flight for one of its waiters: the error itself for the last one,
+/// a copy for variants holding plain values, otherwise a transport error wrapping the shared error.
+fn shared_error(err: Arc<Error>) -> Error {
+    let err = match Arc::try_unwrap(err) {
+        Ok(err) => return err,
+        Err(err) => err,
+    };
+    match &*err {
+        Error::HttpStatus { status, body } => Error::HttpStatus {
+            status: *status,
+            body: body.clone(),
+        },
+        Error::BroadcastRejected { code, message } => Error::BroadcastRejected {
+            code: *code,
+            message: message.clone(),
+        },
+        Error::Parse(message) => Error::Parse(message.clone()),
+        Error::InvalidInput(message) => Error::InvalidInput(message.clone()),
+        Error::Inconsistent(message) => Error::Inconsistent(message.clone()),
+        _ => Error::Transport(Box::new(SharedError(err))),
+    }
+}
+
+/// Error shared by the waiters of a flight.
+#[derive(Debug)]
+struct SharedError(Arc<Error>);
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &*self.0 {
+            // avoid repeating the "transport error" prefix
+            Error::Transport(err) => err.fmt(f),
+            err => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SharedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&*self.0)
+    }
+}
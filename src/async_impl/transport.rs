@@ -0,0 +1,67 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::error::Error;
+use crate::transport::{Request, Response};
+
+/// Future returned by [EsploraTransport::send](EsploraTransport::send).
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, Error>> + Send + 'a>>;
+
+/// HTTP layer used by the async [ApiClient](crate::async_impl::ApiClient).
+///
+/// Implemented for `reqwest::Client`. Implement it to use another HTTP client or to serve canned responses in tests.
+/// Non-2xx responses must be returned as `Ok`, the client turns them into `Error::HttpStatus`.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::async_impl::{ApiClient, EsploraTransport, TransportFuture};
+/// use esplora_api::transport::{Request, Response};
+///
+/// struct Tip;
+///
+/// impl EsploraTransport for Tip {
+///     fn send<'a>(&'a self, _request: &'a Request) -> TransportFuture<'a> {
+///         Box::pin(async { Ok(Response::new(200, "42")) })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), esplora_api::Error> {
+///     let client = ApiClient::with_transport("https://blockstream.info/api", Tip)?;
+///     assert_eq!(client.get_blocks_tip_height().await?, 42);
+///     Ok(())
+/// }
+/// ````
+pub trait EsploraTransport: Send + Sync {
+    /// Perform the HTTP exchange described by request.
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a>;
+}
+
+#[cfg(feature = "reqwest")]
+impl EsploraTransport for reqwest::Client {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let mut builder = match request.method {
+                crate::transport::Method::Get => self.get(&request.url),
+                crate::transport::Method::Post => self.post(&request.url),
+            };
+            if let Some(body) = &request.body {
+                builder = builder.body(body.clone());
+            }
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+            let resp = builder.send().await?;
+            let status = resp.status().as_u16();
+            let headers = resp
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (name.as_str().to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+                })
+                .collect();
+            let body = resp.bytes().await?.to_vec();
+            Ok(Response { status, headers, body })
+        })
+    }
+}
@@ -0,0 +1,480 @@
+//! `esplora` command-line client, built with the `cli` feature.
+//!
+//! Every lookup of the blocking `ApiClient` is a subcommand, e.g. `esplora tx <txid>`, `esplora address utxo <addr>`,
+//! `esplora broadcast <hex|file>`, `esplora fees` or `esplora tip`. Results are printed as JSON, as a table or raw.
+use std::error::Error as StdError;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand};
+use esplora_api::blocking::ApiClient;
+use esplora_api::{Error, Network};
+use serde::Serialize;
+
+mod output;
+
+use self::output::{Format, Output};
+
+#[derive(Parser)]
+#[command(name = "esplora", version, about = "Query an Esplora API from the command line")]
+struct Cli {
+    /// Esplora API url, e.g. https://blockstream.info/api. Defaults to the blockstream.info API of --network.
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Network to query, bitcoin by default: bitcoin, testnet, testnet4, signet, regtest, liquid or liquidtestnet.
+    #[arg(long, global = true)]
+    network: Option<Network>,
+    /// Authorization header sent with every request.
+    #[arg(long, global = true)]
+    auth: Option<String>,
+    /// Output format.
+    #[arg(long, short, global = true, value_enum, default_value_t = Format::Json)]
+    output: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Block by hash, or one of its views.
+    Block(BlockArgs),
+    /// The 10 newest blocks, or a range of blocks.
+    Blocks(BlocksArgs),
+    /// Height of the tip, or its hash with --hash.
+    Tip {
+        #[arg(long)]
+        hash: bool,
+    },
+    /// Transactions by txid, or one of their views.
+    Tx(TxArgs),
+    /// Broadcast a hex transaction given inline, as a file path, or as `-` to read stdin. Prints its txid.
+    Broadcast { transaction: String },
+    /// Information of addresses, or one of their views.
+    Address(AddressArgs),
+    /// Scripthash information, or one of its views.
+    Scripthash(ScriptHashArgs),
+    /// Mempool backlog statistics, or its transactions.
+    Mempool(MempoolArgs),
+    /// Fee estimates in sat/vB by confirmation target.
+    Fees,
+    /// Asset information, or one of its views.
+    #[cfg(feature = "liquid")]
+    Asset(AssetArgs),
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+struct BlockArgs {
+    /// Block hash.
+    hash: Option<String>,
+    #[command(subcommand)]
+    action: Option<BlockAction>,
+}
+
+#[derive(Subcommand)]
+enum BlockAction {
+    /// Confirmation status of the block.
+    Status { hash: String },
+    /// A page of 25 transactions of the block.
+    Txs {
+        hash: String,
+        /// Index of the first transaction, a multiple of 25.
+        #[arg(long)]
+        start: Option<i32>,
+    },
+    /// Every transaction of the block, fetching its pages.
+    Transactions {
+        hash: String,
+        /// Pages fetched at once.
+        #[arg(long, default_value_t = 1)]
+        concurrency: usize,
+    },
+    /// Txids of the block.
+    Txids { hash: String },
+    /// Txid at index in the block.
+    Txid { hash: String, index: i32 },
+    /// Raw block.
+    Raw { hash: String },
+    /// Hash of the best chain block at height.
+    Height { height: i32 },
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+struct BlocksArgs {
+    /// Height of the newest block, the tip by default.
+    #[arg(long)]
+    start: Option<i32>,
+    #[command(subcommand)]
+    action: Option<BlocksAction>,
+}
+
+#[derive(Subcommand)]
+enum BlocksAction {
+    /// Blocks from start to end in ascending order, following the tip when end is omitted.
+    Range {
+        start: u32,
+        end: Option<u32>,
+        /// Seconds between two checks of the tip while waiting for the next block.
+        #[arg(long, default_value_t = 30)]
+        poll_interval: u64,
+    },
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+struct TxArgs {
+    /// Txids, fetched concurrently.
+    txids: Vec<String>,
+    #[command(subcommand)]
+    action: Option<TxAction>,
+}
+
+#[derive(Subcommand)]
+enum TxAction {
+    /// Confirmation status of the transactions.
+    Status {
+        #[arg(required = true)]
+        txids: Vec<String>,
+    },
+    /// Raw transaction.
+    Raw { txid: String },
+    /// Hex transaction.
+    Hex { txid: String },
+    /// Merkle inclusion proof, in Electrum format.
+    MerkleProof { txid: String },
+    /// Merkle inclusion proof, in bitcoind merkleblock format.
+    MerkleblockProof { txid: String },
+    /// Spending status of an output.
+    Outspend { txid: String, vout: i32 },
+    /// Spending status of every output of the transactions.
+    Outspends {
+        #[arg(required = true)]
+        txids: Vec<String>,
+    },
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+struct AddressArgs {
+    /// Addresses, fetched concurrently.
+    addresses: Vec<String>,
+    #[command(subcommand)]
+    action: Option<AddressAction>,
+}
+
+#[derive(Subcommand)]
+enum AddressAction {
+    /// Addresses starting with prefix, at most 10.
+    Prefix { prefix: String },
+    #[command(flatten)]
+    Script(ScriptAction),
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+struct ScriptHashArgs {
+    scripthash: Option<String>,
+    #[command(subcommand)]
+    action: Option<ScriptAction>,
+}
+
+/// Views shared by addresses and scripthashes.
+#[derive(Subcommand)]
+enum ScriptAction {
+    /// Mempool transactions then the 25 newest confirmed transactions.
+    Txs { id: String },
+    /// 25 confirmed transactions, newest first.
+    TxsChain {
+        id: String,
+        /// Txid of the last transaction seen, to get the next page.
+        #[arg(long)]
+        last_seen: Option<String>,
+    },
+    /// Mempool transactions.
+    TxsMempool { id: String },
+    /// Full transaction history, mempool first, fetching pages as needed.
+    History {
+        id: String,
+        /// Only list confirmed transactions.
+        #[arg(long)]
+        skip_mempool: bool,
+        /// Stop at this txid, e.g. the newest transaction already known.
+        #[arg(long)]
+        stop_at_txid: Option<String>,
+        /// Stop at the first transaction confirmed below this height.
+        #[arg(long)]
+        min_height: Option<u32>,
+    },
+    /// Unspent transaction outputs.
+    Utxo { id: String },
+}
+
+#[derive(Args)]
+struct MempoolArgs {
+    #[command(subcommand)]
+    action: Option<MempoolAction>,
+}
+
+#[derive(Subcommand)]
+enum MempoolAction {
+    /// Txids of the mempool.
+    Txids,
+    /// The last 10 transactions to enter the mempool.
+    Recent,
+}
+
+#[cfg(feature = "liquid")]
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+struct AssetArgs {
+    /// Asset id.
+    asset_id: Option<String>,
+    #[command(subcommand)]
+    action: Option<AssetAction>,
+}
+
+#[cfg(feature = "liquid")]
+#[derive(Subcommand)]
+enum AssetAction {
+    /// Mempool transactions then the 25 newest confirmed transactions of the asset.
+    Txs { asset_id: String },
+    /// 25 confirmed transactions of the asset, newest first.
+    TxsChain {
+        asset_id: String,
+        /// Txid of the last transaction seen, to get the next page.
+        #[arg(long)]
+        last_seen: Option<String>,
+    },
+    /// Mempool transactions of the asset.
+    TxsMempool { asset_id: String },
+    /// Total supply of the asset, in base units or with --decimal using its precision.
+    Supply {
+        asset_id: String,
+        #[arg(long)]
+        decimal: bool,
+    },
+    /// Assets of the asset registry.
+    Registry {
+        #[arg(long)]
+        start_index: Option<u32>,
+        #[arg(long)]
+        limit: Option<u32>,
+        #[arg(long)]
+        sort_field: Option<String>,
+        #[arg(long)]
+        sort_dir: Option<String>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn StdError>> {
+    let mut builder = match (&cli.url, cli.network) {
+        (Some(url), Some(network)) => ApiClient::builder().url(url).network(network),
+        (Some(url), None) => ApiClient::builder().url(url),
+        (None, network) => ApiClient::builder().blockstream(network.unwrap_or(Network::Bitcoin)),
+    };
+    if let Some(auth) = &cli.auth {
+        builder = builder.authorization(auth);
+    }
+    let client = builder.build()?;
+    let output = execute(&client, cli.command)?;
+    output::print(output, cli.output)?;
+    Ok(())
+}
+
+fn execute(client: &ApiClient, command: Command) -> Result<Output, Box<dyn StdError>> {
+    let output = match command {
+        Command::Block(args) => match (args.hash, args.action) {
+            (_, Some(action)) => block(client, action)?,
+            (Some(hash), None) => json(client.get_block(&hash)?)?,
+            (None, None) => unreachable!("clap requires a hash or a subcommand"),
+        },
+        Command::Blocks(args) => match args.action {
+            Some(BlocksAction::Range {
+                start,
+                end,
+                poll_interval,
+            }) => {
+                let blocks = match end {
+                    Some(end) => client.blocks_range(start..=end),
+                    None => client.blocks_range(start..),
+                };
+                stream(blocks.poll_interval(Duration::from_secs(poll_interval)))
+            }
+            None => {
+                let start = match args.start {
+                    Some(start) => start,
+                    None => client.get_blocks_tip_height()?,
+                };
+                json(client.get_blocks(start)?)?
+            }
+        },
+        Command::Tip { hash: true } => Output::Text(client.get_blocks_tip_hash()?),
+        Command::Tip { hash: false } => json(client.get_blocks_tip_height()?)?,
+        Command::Tx(args) => match args.action {
+            Some(action) => tx(client, action)?,
+            None => match args.txids.as_slice() {
+                [txid] => json(client.get_tx(txid)?)?,
+                txids => json(collect(client.get_txs(txids))?)?,
+            },
+        },
+        Command::Broadcast { transaction } => Output::Text(client.post_tx(&read_transaction(&transaction)?)?),
+        Command::Address(args) => match args.action {
+            Some(AddressAction::Prefix { prefix }) => json(client.get_address_prefix(&prefix)?)?,
+            Some(AddressAction::Script(action)) => script(client, action, false)?,
+            None => match args.addresses.as_slice() {
+                [address] => json(client.get_address(address)?)?,
+                addresses => json(collect(client.get_addresses_many(addresses))?)?,
+            },
+        },
+        Command::Scripthash(args) => match (args.scripthash, args.action) {
+            (_, Some(action)) => script(client, action, true)?,
+            (Some(scripthash), None) => json(client.get_script_hash(&scripthash)?)?,
+            (None, None) => unreachable!("clap requires a scripthash or a subcommand"),
+        },
+        Command::Mempool(args) => match args.action {
+            None => json(client.get_mempool()?)?,
+            Some(MempoolAction::Txids) => json(client.get_mempool_txids()?)?,
+            Some(MempoolAction::Recent) => json(client.get_mempool_recent()?)?,
+        },
+        Command::Fees => json(client.fee_estimate()?)?,
+        #[cfg(feature = "liquid")]
+        Command::Asset(args) => match (args.asset_id, args.action) {
+            (_, Some(action)) => asset(client, action)?,
+            (Some(asset_id), None) => json(client.get_asset(&asset_id)?)?,
+            (None, None) => unreachable!("clap requires an asset id or a subcommand"),
+        },
+    };
+    Ok(output)
+}
+
+fn block(client: &ApiClient, action: BlockAction) -> Result<Output, Box<dyn StdError>> {
+    Ok(match action {
+        BlockAction::Status { hash } => json(client.get_block_status(&hash)?)?,
+        BlockAction::Txs { hash, start } => json(client.get_block_txs(&hash, start)?)?,
+        BlockAction::Transactions { hash, concurrency } => {
+            stream(client.block_transactions(&hash).concurrency(concurrency))
+        }
+        BlockAction::Txids { hash } => json(client.get_block_txids(&hash)?)?,
+        BlockAction::Txid { hash, index } => Output::Text(client.get_block_txid_at_index(&hash, index)?),
+        BlockAction::Raw { hash } => Output::Bytes(client.get_block_raw_format(&hash)?),
+        BlockAction::Height { height } => Output::Text(client.get_block_height(height)?),
+    })
+}
+
+fn tx(client: &ApiClient, action: TxAction) -> Result<Output, Box<dyn StdError>> {
+    Ok(match action {
+        TxAction::Status { txids } => match txids.as_slice() {
+            [txid] => json(client.get_tx_status(txid)?)?,
+            txids => json(collect(client.get_tx_statuses(txids))?)?,
+        },
+        TxAction::Raw { txid } => Output::Bytes(client.get_tx_raw(&txid)?),
+        TxAction::Hex { txid } => Output::Text(client.get_tx_hex(&txid)?),
+        TxAction::MerkleProof { txid } => json(client.get_tx_merkle_proof(&txid)?)?,
+        TxAction::MerkleblockProof { txid } => Output::Text(client.get_tx_merkleblock_proof(&txid)?),
+        TxAction::Outspend { txid, vout } => json(client.get_tx_outspend(&txid, Some(vout))?)?,
+        TxAction::Outspends { txids } => match txids.as_slice() {
+            [txid] => json(client.get_tx_outspends(txid)?)?,
+            txids => json(collect(client.get_outspends_many(txids))?)?,
+        },
+    })
+}
+
+/// Views of an address, or of a scripthash when script_hash is set.
+fn script(client: &ApiClient, action: ScriptAction, script_hash: bool) -> Result<Output, Box<dyn StdError>> {
+    Ok(match action {
+        ScriptAction::Txs { id } if script_hash => json(client.get_script_hash_txs(&id)?)?,
+        ScriptAction::Txs { id } => json(client.get_address_txs(&id)?)?,
+        ScriptAction::TxsChain { id, last_seen } if script_hash => {
+            json(client.get_script_hash_txs_chain(&id, last_seen.as_deref())?)?
+        }
+        ScriptAction::TxsChain { id, last_seen } => json(client.get_address_txs_chain(&id, last_seen.as_deref())?)?,
+        ScriptAction::TxsMempool { id } if script_hash => json(client.get_script_hash_txs_mempool(&id)?)?,
+        ScriptAction::TxsMempool { id } => json(client.get_address_txs_mempool(&id)?)?,
+        ScriptAction::Utxo { id } if script_hash => json(client.get_script_hash_utxo(&id)?)?,
+        ScriptAction::Utxo { id } => json(client.get_address_utxo(&id)?)?,
+        ScriptAction::History {
+            id,
+            skip_mempool,
+            stop_at_txid,
+            min_height,
+        } => {
+            let mut history = if script_hash {
+                client.script_hash_history(&id)
+            } else {
+                client.address_history(&id)
+            };
+            if skip_mempool {
+                history = history.skip_mempool();
+            }
+            if let Some(txid) = stop_at_txid {
+                history = history.stop_at_txid(txid);
+            }
+            if let Some(height) = min_height {
+                history = history.min_height(height);
+            }
+            stream(history)
+        }
+    })
+}
+
+#[cfg(feature = "liquid")]
+fn asset(client: &ApiClient, action: AssetAction) -> Result<Output, Box<dyn StdError>> {
+    Ok(match action {
+        AssetAction::Txs { asset_id } => json(client.get_asset_txs(&asset_id)?)?,
+        AssetAction::TxsChain { asset_id, last_seen } => {
+            json(client.get_asset_txs_chain(&asset_id, last_seen.as_deref())?)?
+        }
+        AssetAction::TxsMempool { asset_id } => json(client.get_asset_txs_mempool(&asset_id)?)?,
+        AssetAction::Supply { asset_id, decimal: true } => Output::Text(client.get_asset_supply_decimal(&asset_id)?),
+        AssetAction::Supply { asset_id, decimal: false } => json(client.get_asset_supply(&asset_id)?)?,
+        AssetAction::Registry {
+            start_index,
+            limit,
+            sort_field,
+            sort_dir,
+        } => json(client.get_assets_registry(start_index, limit, sort_field.as_deref(), sort_dir.as_deref())?)?,
+    })
+}
+
+fn json<T: Serialize>(value: T) -> Result<Output, Box<dyn StdError>> {
+    Ok(Output::Value(serde_json::to_value(value)?))
+}
+
+/// Output items of an iterator as they are fetched.
+fn stream<T: Serialize>(items: impl Iterator<Item = Result<T, Error>> + 'static) -> Output {
+    Output::Stream(Box::new(items.map(|item| Ok(serde_json::to_value(item?)?))))
+}
+
+/// Results of a batch call, failing on the first error.
+fn collect<T>(results: Vec<Result<T, Error>>) -> Result<Vec<T>, Error> {
+    results.into_iter().collect()
+}
+
+/// Hex transaction given inline, as a file path, or as `-` for stdin.
+fn read_transaction(transaction: &str) -> io::Result<String> {
+    let hex = if transaction == "-" {
+        let mut hex = String::new();
+        io::stdin().read_to_string(&mut hex)?;
+        hex
+    } else if Path::new(transaction).is_file() {
+        fs::read_to_string(transaction)?
+    } else {
+        transaction.to_string()
+    };
+    Ok(hex.trim().to_string())
+}
@@ -0,0 +1,164 @@
+use std::error::Error as StdError;
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Pretty-printed JSON, one compact JSON document per line for listings fetched page by page.
+    Json,
+    /// Aligned key / value rows for an object, one row per item for a list.
+    Table,
+    /// Strings without quotes, compact JSON, and binary data as is.
+    Raw,
+}
+
+/// Result of a command.
+pub enum Output {
+    Value(Value),
+    Text(String),
+    Bytes(Vec<u8>),
+    /// Items printed as they are fetched, e.g. a full address history.
+    Stream(Box<dyn Iterator<Item = Result<Value, Box<dyn StdError>>>>),
+}
+
+pub fn print(output: Output, format: Format) -> Result<(), Box<dyn StdError>> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match (output, format) {
+        (Output::Value(value), Format::Json) => writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?,
+        (Output::Value(value), Format::Raw) => writeln!(out, "{}", raw(&value))?,
+        (Output::Value(value), Format::Table) => print_table(&mut out, &value)?,
+        (Output::Text(text), Format::Json) => writeln!(out, "{}", Value::String(text))?,
+        (Output::Text(text), _) => writeln!(out, "{}", text)?,
+        (Output::Bytes(bytes), Format::Raw) => out.write_all(&bytes)?,
+        (Output::Bytes(bytes), Format::Json) => writeln!(out, "{}", Value::String(hex(&bytes)))?,
+        (Output::Bytes(bytes), Format::Table) => writeln!(out, "{}", hex(&bytes))?,
+        (Output::Stream(items), format) => {
+            let mut columns: Option<Vec<String>> = None;
+            for item in items {
+                let item = item?;
+                match format {
+                    Format::Json => writeln!(out, "{}", item)?,
+                    Format::Raw => writeln!(out, "{}", raw(&item))?,
+                    Format::Table => {
+                        // widths are unknown before the end of the stream, columns are tab separated
+                        let cells = flatten(&item, false);
+                        let columns = columns.get_or_insert_with(|| {
+                            let header: Vec<String> = cells.iter().map(|(key, _)| key.clone()).collect();
+                            let _ = writeln!(out, "{}", header.join("\t"));
+                            header
+                        });
+                        writeln!(out, "{}", row(columns, &cells).join("\t"))?;
+                    }
+                }
+                out.flush()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Strings without quotes, other values as compact JSON.
+fn raw(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn print_table(out: &mut impl Write, value: &Value) -> io::Result<()> {
+    match value {
+        Value::Object(_) => {
+            let rows: Vec<Vec<String>> = flatten(value, true).into_iter().map(|(key, cell)| vec![key, cell]).collect();
+            print_rows(out, &rows)
+        }
+        Value::Array(items) if items.iter().all(Value::is_object) && !items.is_empty() => {
+            let items: Vec<Vec<(String, String)>> = items.iter().map(|item| flatten(item, false)).collect();
+            let mut columns: Vec<String> = Vec::new();
+            for (key, _) in items.iter().flatten() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+            let mut rows = vec![columns.clone()];
+            rows.extend(items.iter().map(|cells| row(&columns, cells)));
+            print_rows(out, &rows)
+        }
+        Value::Array(items) => items.iter().try_for_each(|item| writeln!(out, "{}", cell(item))),
+        value => writeln!(out, "{}", cell(value)),
+    }
+}
+
+/// Cells of an item in the order of columns, `-` when missing.
+fn row(columns: &[String], cells: &[(String, String)]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| {
+            cells
+                .iter()
+                .find(|(key, _)| key == column)
+                .map_or_else(|| "-".to_string(), |(_, cell)| cell.clone())
+        })
+        .collect()
+}
+
+/// Leaves of value with their dotted path, e.g. `status.confirmed`.
+/// Arrays are expanded with their indexes when expand_arrays is set, otherwise summarized by their length.
+fn flatten(value: &Value, expand_arrays: bool) -> Vec<(String, String)> {
+    fn walk(value: &Value, path: String, expand_arrays: bool, cells: &mut Vec<(String, String)>) {
+        let child = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+        match value {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    walk(value, child(key), expand_arrays, cells);
+                }
+            }
+            Value::Array(items) if expand_arrays && !items.is_empty() => {
+                for (index, item) in items.iter().enumerate() {
+                    walk(item, child(&index.to_string()), expand_arrays, cells);
+                }
+            }
+            Value::Array(items) => cells.push((path, format!("[{}]", items.len()))),
+            value => cells.push((path, cell(value))),
+        }
+    }
+    let mut cells = Vec::new();
+    walk(value, String::new(), expand_arrays, &mut cells);
+    cells
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        value => raw(value),
+    }
+}
+
+/// Rows with aligned columns.
+fn print_rows(out: &mut impl Write, rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            match widths.get_mut(index) {
+                Some(width) => *width = (*width).max(cell.chars().count()),
+                None => widths.push(cell.chars().count()),
+            }
+        }
+    }
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
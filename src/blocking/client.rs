@@ -1,27 +1,189 @@
 use std::collections::HashMap;
-use reqwest;
+use std::fmt;
+use std::ops::RangeBounds;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::cache::{BestChainBlocks, CachePolicy, CacheStats, ResponseCache};
 use crate::data::blockstream::{
     AddressInfoFormat, BlockFormat, BlockStatus, MemPoolFormat, MempoolTxFormat, MerkleProofFormat,
     OutspentFormat, TransactionFormat, TxStatusFormat, UtxoFormat,
 };
-/// Client to call esplora api, it use and Esplora Api Url. I can use custom reqwest Client build from reqwest client builder
-#[derive(Debug)]
+#[cfg(feature = "bitcoin")]
+use crate::data::bitcoin_types::{consensus_decode, parse_txid};
+#[cfg(feature = "liquid")]
+use crate::data::liquid::AssetFormat;
+use crate::endpoint::{self, Endpoint};
+use crate::error::Error;
+use crate::failover::{self, EndpointHealth, Endpoints, Failover, Served};
+use crate::history::{History, Target};
+use crate::network::Network;
+use crate::options::{ClientOptions, DEFAULT_BATCH_CONCURRENCY};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::store::{CacheStore, PersistentCache};
+use crate::transport::{Request, Response};
+use super::{BlockTransactions, BlocksRange, EsploraTransport, TxHistory};
+/// Client to call esplora api, it use and Esplora Api Url. I can use custom reqwest Client build from reqwest client builder,
+/// or any other HTTP client implementing [EsploraTransport](EsploraTransport).
+/// Clones share the same transport.
+#[derive(Clone)]
 pub struct ApiClient {
     pub url: String,
-    pub reqwest: reqwest::blocking::Client,
+    transport: Arc<dyn EsploraTransport>,
+    retry: Option<RetryPolicy>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    endpoints: Option<Arc<Endpoints>>,
+    timeout: Option<Duration>,
+    batch_concurrency: usize,
+    cache: Option<Arc<ResponseCache>>,
+    store: Option<Arc<PersistentCache>>,
+    /// Blocks recently seen in the best chain, see `still_confirmed`.
+    best_chain: Arc<BestChainBlocks>,
+    network: Option<Network>,
+    /// Url of the endpoint that served the latest response, recorded for [served](ApiClient::served).
+    served: Option<Arc<Mutex<Option<String>>>>,
+}
+impl fmt::Debug for ApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiClient").field("url", &self.url).finish()
+    }
 }
-/// Client basics options used to custom reqwest client
-#[derive(Debug)]
-pub struct ClientOptions {
-    pub headers: Option<HeadersOptions>,
+/// Builder of [ApiClient](ApiClient), see [ApiClient::builder](ApiClient::builder).
+#[derive(Default)]
+pub struct ApiClientBuilder {
+    url: Option<String>,
+    network: Option<Network>,
+    /// Network not served by the provider selected with a preset.
+    unsupported: Option<String>,
+    options: ClientOptions,
+    transport: Option<Arc<dyn EsploraTransport>>,
 }
-/// Headers options can be used to use authorization header
-#[derive(Debug)]
-pub struct HeadersOptions {
-    pub authorization: Option<String>,
+impl fmt::Debug for ApiClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiClientBuilder")
+            .field("url", &self.url)
+            .field("network", &self.network)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+impl ApiClientBuilder {
+    /// Esplora Api Url, e.g. `https://blockstream.info/api`. A trailing slash is ignored.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self.unsupported = None;
+        self
+    }
+    /// Network served by the url, remembered by the client.
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+    /// Use the blockstream.info API of network.
+    pub fn blockstream(self, network: Network) -> Self {
+        let url = network.blockstream_url().map(str::to_string);
+        self.preset(network, url, "blockstream.info")
+    }
+    /// Use the mempool.space API of network, liquid.network for Liquid networks.
+    pub fn mempool_space(self, network: Network) -> Self {
+        let url = network.mempool_space_url().map(str::to_string);
+        self.preset(network, url, "mempool.space")
+    }
+    /// Set url and network, build fails when provider does not serve network.
+    fn preset(mut self, network: Network, url: Option<String>, provider: &str) -> Self {
+        self.network = Some(network);
+        self.unsupported = match url {
+            Some(_) => None,
+            None => Some(format!("{} does not serve {}", provider, network)),
+        };
+        self.url = url;
+        self
+    }
+    /// Send requests through transport instead of a reqwest client built from the options.
+    /// HTTP options (headers, user agent, connect timeout, proxy, TLS) are left to the transport, so building fails
+    /// if they are set too. The total timeout is given to the transport with each request.
+    pub fn transport(mut self, transport: impl EsploraTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+    client_option_list!(builder_setters);
+    /// Validate the url and the options and build the client.
+    pub fn build(self) -> Result<ApiClient, Error> {
+        if let Some(unsupported) = self.unsupported {
+            return Err(Error::InvalidInput(unsupported));
+        }
+        let url = self.url.ok_or_else(|| Error::InvalidInput("missing esplora url".to_string()))?;
+        let url = endpoint::base_url(&url)?;
+        let options = self.options;
+        let transport = match self.transport {
+            Some(transport) => {
+                options.check_custom_transport()?;
+                transport
+            }
+            #[cfg(feature = "reqwest")]
+            None => Arc::new(options.reqwest_blocking_client()?),
+            #[cfg(not(feature = "reqwest"))]
+            None => return Err(Error::InvalidInput("missing transport".to_string())),
+        };
+        let mut client = ApiClient::from_shared_transport(url, transport).with_options(options)?;
+        client.network = self.network;
+        Ok(client)
+    }
 }
 impl ApiClient {
-    /// new client from endpoint Esplora Api Url, and ClientOptions.
+    /// builder new client builder, validating the url and the options.
+    ///
+    /// Example :
+    /// ````rust
+    /// use std::time::Duration;
+    /// use esplora_api::blocking::ApiClient;
+    ///
+    /// fn main() -> Result<(), esplora_api::Error> {
+    ///     let client = ApiClient::builder()
+    ///         .url("https://blockstream.info/testnet/api/")
+    ///         .timeout(Duration::from_secs(30))
+    ///         .authorization("secret")
+    ///         .build()?;
+    ///     assert_eq!(client.url, "https://blockstream.info/testnet/api");
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn builder() -> ApiClientBuilder {
+        ApiClientBuilder::default()
+    }
+    /// blockstream new client using the blockstream.info API of network.
+    /// Fails for networks not served by blockstream.info (testnet4, regtest).
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::blocking::ApiClient;
+    /// use esplora_api::Network;
+    ///
+    /// fn main() -> Result<(), esplora_api::Error> {
+    ///     let client = ApiClient::blockstream(Network::Testnet)?;
+    ///     assert_eq!(client.url, "https://blockstream.info/testnet/api");
+    ///     assert_eq!(client.network(), Some(Network::Testnet));
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "reqwest")]
+    pub fn blockstream(network: Network) -> Result<Self, Error> {
+        ApiClient::builder().blockstream(network).build()
+    }
+    /// mempool_space new client using the mempool.space API of network, liquid.network for Liquid networks.
+    /// Fails for regtest.
+    #[cfg(feature = "reqwest")]
+    pub fn mempool_space(network: Network) -> Result<Self, Error> {
+        ApiClient::builder().mempool_space(network).build()
+    }
+    /// network Network served by the client, when known.
+    pub fn network(&self) -> Option<Network> {
+        self.network
+    }
+    /// new client from endpoint Esplora Api Url, and ClientOptions. Same as [ApiClient::builder](ApiClient::builder) with url and options.
     /// 
     /// Example without options :
     /// ````rust
@@ -34,44 +196,40 @@ impl ApiClient {
     /// ````rust
     /// use esplora_api::blocking::{ApiClient, ClientOptions, HeadersOptions};
     /// fn main(){
-    ///     let options = ClientOptions { headers: Some( HeadersOptions { authorization: Some("secret".to_string())}),};
+    ///     let options = ClientOptions { headers: Some( HeadersOptions { authorization: Some("secret".to_string())}), ..Default::default()};
+    ///     let client = esplora_api::blocking::ApiClient::new("https://some_esplora_url.com", Some(options));
+    /// }
+    /// ````
+    /// Example with rate limit :
+    /// ````rust
+    /// use esplora_api::blocking::{ApiClient, ClientOptions};
+    /// use esplora_api::RateLimit;
+    ///
+    /// fn main() -> Result<(), esplora_api::Error> {
+    ///     let options = ClientOptions { rate_limit: Some(RateLimit::new(5.0, 10)?), ..Default::default()};
+    ///     let client = esplora_api::blocking::ApiClient::new("https://some_esplora_url.com", Some(options))?;
+    ///     Ok(())
+    /// }
+    /// ````
+    /// Example with retries :
+    /// ````rust
+    /// use esplora_api::blocking::{ApiClient, ClientOptions};
+    /// use esplora_api::RetryPolicy;
+    ///
+    /// fn main(){
+    ///     let options = ClientOptions { retry: Some(RetryPolicy::default()), ..Default::default()};
     ///     let client = esplora_api::blocking::ApiClient::new("https://some_esplora_url.com", Some(options));
     /// }
     /// ````
+    #[cfg(feature = "reqwest")]
     pub fn new(
         url: &str,
         options: Option<ClientOptions>,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut client_builder = reqwest::blocking::ClientBuilder::new();
-        // Find options
-        match options {
-            // Build headers
-            Some(ClientOptions { headers, .. }) => {
-                let mut headers_map = reqwest::header::HeaderMap::new();
-                match headers {
-                    // header::AUTHORIZATION
-                    Some(HeadersOptions {
-                        authorization: Some(authorization),
-                    }) => {
-                        headers_map.insert(
-                            reqwest::header::AUTHORIZATION,
-                            reqwest::header::HeaderValue::from_str(&authorization).unwrap(),
-                        );
-                    }
-                    _ => (),
-                }
-                client_builder = client_builder.default_headers(headers_map);
-            }
-            None => (),
-        }
-        let build = client_builder
+    ) -> Result<Self, Error> {
+        ApiClient::builder()
+            .url(url)
+            .options(options.unwrap_or_default())
             .build()
-            .unwrap_or(reqwest::blocking::Client::new());
-
-        Ok(ApiClient {
-            url: url.to_string(),
-            reqwest: build,
-        })
     }
     /// new_from_config new client from endpoint Esplora Api Url, and reqwest client.
     /// 
@@ -87,14 +245,196 @@ impl ApiClient {
     ///     let client = esplora_api::blocking::ApiClient::new_from_config("https://some_esplora_url.com", reqwest_client);
     /// }
     /// ````
+    #[cfg(feature = "reqwest")]
     pub fn new_from_config(
         url: &str,
-        client: reqwest::blocking::Client
-    )->Result<Self, Box<dyn std::error::Error>> {
-        Ok(ApiClient {
-            url: url.to_string(),
-            reqwest: client,
-        })
+        client: reqwest::blocking::Client,
+    ) -> Result<Self, Error> {
+        ApiClient::builder().url(url).transport(client).build()
+    }
+    /// with_transport new client from endpoint Esplora Api Url, sending its requests through transport.
+    /// Same as [ApiClient::builder](ApiClient::builder) with url and transport.
+    ///
+    /// See [EsploraTransport](EsploraTransport) for an example.
+    pub fn with_transport(url: &str, transport: impl EsploraTransport + 'static) -> Result<Self, Error> {
+        ApiClient::builder().url(url).transport(transport).build()
+    }
+    fn from_shared_transport(url: String, transport: Arc<dyn EsploraTransport>) -> Self {
+        ApiClient {
+            url,
+            transport,
+            retry: None,
+            rate_limiter: None,
+            endpoints: None,
+            timeout: None,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            cache: None,
+            store: None,
+            best_chain: Arc::new(BestChainBlocks::new(CachePolicy::default().ttl)),
+            network: None,
+            served: None,
+        }
+    }
+    /// Apply the options handled by the client itself rather than by its transport.
+    fn with_options(mut self, options: ClientOptions) -> Result<Self, Error> {
+        if let Some(timeout) = options.timeout {
+            self.timeout = Some(timeout);
+        }
+        if let Some(retry) = options.retry {
+            self = self.with_retry(retry);
+        }
+        if let Some(rate_limit) = options.rate_limit {
+            self = self.with_rate_limit(rate_limit);
+        }
+        if let Some(failover) = options.failover {
+            self = self.with_failover(failover)?;
+        }
+        if let Some(concurrency) = options.batch_concurrency {
+            self = self.with_batch_concurrency(concurrency);
+        }
+        if let Some(cache) = options.cache {
+            self = self.with_cache(cache);
+        }
+        if let Some(store) = options.persistent_cache {
+            self.store = Some(Arc::new(PersistentCache::new(store)));
+        }
+        Ok(self)
+    }
+    /// with_timeout set the total timeout of each request sent by this client, overriding `ClientOptions::timeout`.
+    /// Clone the client to override it for a single call.
+    ///
+    /// Example :
+    /// ````rust
+    /// use std::time::Duration;
+    ///
+    /// fn run(client: &esplora_api::blocking::ApiClient) {
+    ///     // raw blocks can weigh 4 MB
+    ///     let slow_client = client.clone().with_timeout(Duration::from_secs(120));
+    /// }
+    /// ````
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// with_retry retry transient failures of every call according to retry.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+    /// with_rate_limit limit the rate of requests sent by this client and its clones.
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(rate_limit)));
+        self
+    }
+    /// with_failover fall back to other endpoints when url is unavailable.
+    /// Health of the endpoints is shared by the clones of this client.
+    /// Fallback urls are validated and normalized like the client url, fails if one is invalid.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::Failover;
+    ///
+    /// fn main() -> Result<(), esplora_api::Error> {
+    ///     let failover = Failover { fallback_urls: vec!["https://blockstream.info/api".to_string()], ..Default::default() };
+    ///     let client = esplora_api::blocking::ApiClient::new("https://electrs.local/api", None)?.with_failover(failover)?;
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn with_failover(mut self, mut failover: Failover) -> Result<Self, Error> {
+        failover.fallback_urls = failover
+            .fallback_urls
+            .iter()
+            .map(|url| endpoint::base_url(url))
+            .collect::<Result<_, _>>()?;
+        self.endpoints = Some(Arc::new(Endpoints::new(&self.url, failover)));
+        Ok(self)
+    }
+    /// with_batch_concurrency number of requests sent at once, each on its own thread by the batch methods such as `get_txs`, 8 by default.
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency.max(1);
+        self
+    }
+    /// with_cache cache responses in memory according to cache, sharing the cache with the clones of this client.
+    /// See [CachePolicy](crate::CachePolicy) for the routes cached.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::CachePolicy;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/api", None)?.with_cache(CachePolicy::default());
+    ///     println!("{:?}", client.cache_stats());
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn with_cache(mut self, cache: CachePolicy) -> Self {
+        self.best_chain = Arc::new(BestChainBlocks::new(cache.ttl));
+        self.cache = Some(Arc::new(ResponseCache::new(cache)));
+        self
+    }
+    /// cache_stats hits, misses and size of the cache, None without cache.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+    /// with_persistent_cache keep blocks by hash, their transactions, raw blocks and confirmed transactions in store,
+    /// answering later calls from it, also across restarts. Before a cached confirmed transaction is served, the status
+    /// of its block is fetched again unless it was checked less than the cache ttl ago (10s without cache). Responses tied to a block are removed from both caches when a block status,
+    /// including the one fetched when a `blocks_range` link breaks, shows the block left the best chain,
+    /// see also [invalidate_block](ApiClient::invalidate_block).
+    /// Checked after the in-memory cache if any. Also set with `ClientOptions::persistent_cache`.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::FsStore;
+    ///
+    /// fn run() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/api", None)?
+    ///         .with_persistent_cache(FsStore::new("/var/cache/esplora")?);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn with_persistent_cache(mut self, store: impl CacheStore + 'static) -> Self {
+        self.store = Some(Arc::new(PersistentCache::new(Arc::new(store))));
+        self
+    }
+    /// invalidate_block remove the responses tied to the block from the in-memory and persistent caches, e.g. after a reorg.
+    pub fn invalidate_block(&self, hash: &str) -> std::io::Result<()> {
+        self.best_chain.remove(hash);
+        if let Some(cache) = &self.cache {
+            cache.invalidate(hash);
+        }
+        match &self.store {
+            Some(store) => store.invalidate(hash),
+            None => Ok(()),
+        }
+    }
+    /// endpoints health of the endpoints of a failover client, empty without failover.
+    pub fn endpoints(&self) -> Vec<EndpointHealth> {
+        self.endpoints.as_ref().map(|endpoints| endpoints.health()).unwrap_or_default()
+    }
+    /// served run call with a clone of this client and return its result with the url of the endpoint that served it,
+    /// the last one when call sends several requests. Calls running at the same time each get their own endpoint.
+    /// Always the client url without failover, None when answered from a cache.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::Failover;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let failover = Failover { fallback_urls: vec!["https://blockstream.info/api".to_string()], ..Default::default() };
+    ///     let client = esplora_api::blocking::ApiClient::new("https://electrs.local/api", None)?.with_failover(failover)?;
+    ///     let served = client.served(|client| client.get_blocks_tip_height())?;
+    ///     println!("height {} from {:?}", served.value, served.url);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn served<T>(&self, call: impl FnOnce(&ApiClient) -> Result<T, Error>) -> Result<Served<T>, Error> {
+        let slot = Arc::new(Mutex::new(None));
+        let mut client = self.clone();
+        client.served = Some(slot.clone());
+        let value = call(&client)?;
+        let url = slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        Ok(Served { url, value })
     }
     /// get_block Returns information about a block.
     ///
@@ -105,18 +445,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7").unwrap();
+    ///     let response = client.get_block("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_block(&self, hash: &str) -> Result<BlockFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/block/{}", self.url, hash);
-        let resp: BlockFormat = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_block(&self, hash: &str) -> Result<BlockFormat, Error> {
+        self.call(endpoint::block(hash))
     }
     /// get_block_status Returns the block status.
     ///
@@ -124,18 +463,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_status("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7").unwrap();
+    ///     let response = client.get_block_status("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_block_status(&self, hash: &str) -> Result<BlockStatus, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/block/{}/status", self.url, hash);
-        let resp: BlockStatus = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_block_status(&self, hash: &str) -> Result<BlockStatus, Error> {
+        self.call(endpoint::block_status(hash))
     }
     /// get_block_txs Returns a list of transactions in the block (up to 25 transactions beginning at start_index).
     ///
@@ -146,26 +484,43 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_txs("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7", Some(25)).unwrap();
+    ///     let response = client.get_block_txs("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7", Some(25))?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_block_txs(
         &self,
         hash: &str,
         start_index: Option<i32>,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = if let Some(i) = start_index {
-            format!("{}/block/{}/txs/{}", self.url, hash, i)
-        } else {
-            format!("{}/block/{}/txs", self.url, hash)
-        };
-        let resp: Vec<TransactionFormat> = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::block_txs(hash, start_index))
+    }
+
+    /// block_transactions Iterator over every transaction of the block, in block order, fetched 25 per page.
+    /// Each page is checked against the block `tx_count`, a missing transaction ends the iterator with `Error::Inconsistent`.
+    /// See [BlockTransactions](BlockTransactions) to fetch several pages at once.
+    ///
+    /// Route : GET /block/:hash then GET /block/:hash/txs/:start_index
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     for tx in client.block_transactions("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7").concurrency(4) {
+    ///         println!("{:?}",tx?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn block_transactions(&self, hash: &str) -> BlockTransactions {
+        BlockTransactions::new(self.clone(), hash)
     }
     /// get_block_txids Returns a list of all txids in the block.
     ///
@@ -175,18 +530,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_txids("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7").unwrap();
+    ///     let response = client.get_block_txids("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_block_txids(&self, hash: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/block/{}/txids", self.url, hash);
-        let resp: Vec<String> = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_block_txids(&self, hash: &str) -> Result<Vec<String>, Error> {
+        self.call(endpoint::block_txids(hash))
     }
     /// get_block_txid_at_index Returns the transaction at index :index within the specified block.
     ///
@@ -196,27 +550,21 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_txid_at_index("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7",25).unwrap();
+    ///     let response = client.get_block_txid_at_index("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7",25)?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_block_txid_at_index(
         &self,
         hash: &str,
         index: i32,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!(
-            "{}/block/{}/txid/{}",
-            self.url,
-            hash,
-            index.to_string()
-        );
-        let resp: String = self.reqwest.get(&request_url).send()?.text()?;
-        Ok(resp.clone())
+    ) -> Result<String, Error> {
+        self.call(endpoint::block_txid_at_index(hash, index))
     }
     /// get_block_raw_format Returns the raw block representation in binary.
     ///
@@ -226,18 +574,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_raw_format("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7").unwrap();
+    ///     let response = client.get_block_raw_format("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_block_raw_format(&self, hash: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/block/{}/raw", self.url, hash);
-        let resp = self.reqwest.get(&request_url).send()?.bytes()?.to_vec();
-        Ok(resp)
+    pub fn get_block_raw_format(&self, hash: &str) -> Result<Vec<u8>, Error> {
+        self.call(endpoint::block_raw(hash))
     }
 
     /// get_block_height Returns the hash of the block currently at height.
@@ -246,18 +593,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_block_height(424242).unwrap();
+    ///     let response = client.get_block_height(424242)?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_block_height(&self, height: i32) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/block-height/{}", self.url, height);
-        let resp = self.reqwest.get(&request_url).send()?.text()?;
-        Ok(resp)
+    pub fn get_block_height(&self, height: i32) -> Result<String, Error> {
+        self.call(endpoint::block_height(height))
     }
     /// get_blocks Returns the 10 newest blocks starting at the tip or at start_height if specified.
     ///
@@ -265,21 +611,43 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_blocks(1234).unwrap();
+    ///     let response = client.get_blocks(1234)?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_blocks(
         &self,
         start_height: i32,
-    ) -> Result<Vec<BlockFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/blocks/{}", self.url, start_height);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<BlockFormat>, Error> {
+        self.call(endpoint::blocks(start_height))
+    }
+
+    /// blocks_range Iterator over the blocks of the height range in ascending order, fetched 10 per page.
+    /// Each block is checked to follow the previous one through `previousblockhash`.
+    /// Heights above the tip are awaited: an unbounded range like `height..` follows the tip indefinitely.
+    /// See [BlocksRange](BlocksRange) to set how often the tip is checked.
+    ///
+    /// Route : GET /blocks/tip/height then GET /blocks/:start_height
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     for block in client.blocks_range(1_900_000..=1_900_100) {
+    ///         println!("{:?}",block?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn blocks_range(&self, range: impl RangeBounds<u32>) -> BlocksRange {
+        BlocksRange::new(self.clone(), range)
     }
     /// get_blocks_tip_height Returns the height of the last block.
     ///
@@ -287,18 +655,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_blocks_tip_height().unwrap();
+    ///     let response = client.get_blocks_tip_height()?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_blocks_tip_height(&self) -> Result<i32, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/blocks/tip/height", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.text()?.parse()?;
-        Ok(resp)
+    pub fn get_blocks_tip_height(&self) -> Result<i32, Error> {
+        self.call(endpoint::blocks_tip_height())
     }
     /// get_blocks_tip_hash Returns the hash of the last block.
     ///
@@ -306,19 +673,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_blocks_tip_height().unwrap();
+    ///     let response = client.get_blocks_tip_height()?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-
-    pub fn get_blocks_tip_hash(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/blocks/tip/hash", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.text()?;
-        Ok(resp)
+    pub fn get_blocks_tip_hash(&self) -> Result<String, Error> {
+        self.call(endpoint::blocks_tip_hash())
     }
     /// get_tx Returns information about the transaction. Available fields: txid, version, locktime, size, weight, fee, vin, vout and status (see transaction format for details).
     ///
@@ -326,18 +691,36 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_tx(&self, txid: &str) -> Result<TransactionFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_tx(&self, txid: &str) -> Result<TransactionFormat, Error> {
+        self.call(endpoint::tx(txid))
+    }
+    /// get_txs Returns the transactions of txids, sending up to `batch_concurrency` requests at once.
+    /// Returns one result per item in input order, like [get_tx](ApiClient::get_tx): a missing item does not fail the others.
+    ///
+    /// Route : GET /tx/:txid for each item
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let responses = client.get_txs(&["d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc", "c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24"]);
+    ///     println!("{:?}",responses);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn get_txs<S: AsRef<str> + Sync>(&self, txids: &[S]) -> Vec<Result<TransactionFormat, Error>> {
+        self.call_many(txids, endpoint::tx)
     }
     /// get_tx_status Returns the transaction confirmation status. Available fields: confirmed (boolean), block_height (optional) and block_hash (optional).
     ///
@@ -345,18 +728,36 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_status("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx_status("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_tx_status(&self, txid: &str) -> Result<TxStatusFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/status", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_tx_status(&self, txid: &str) -> Result<TxStatusFormat, Error> {
+        self.call(endpoint::tx_status(txid))
+    }
+    /// get_tx_statuses Returns the confirmation status of the transactions of txids, sending up to `batch_concurrency` requests at once.
+    /// Returns one result per item in input order, like [get_tx_status](ApiClient::get_tx_status): a missing item does not fail the others.
+    ///
+    /// Route : GET /tx/:txid/status for each item
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let responses = client.get_tx_statuses(&["d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc"]);
+    ///     println!("{:?}",responses);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn get_tx_statuses<S: AsRef<str> + Sync>(&self, txids: &[S]) -> Vec<Result<TxStatusFormat, Error>> {
+        self.call_many(txids, endpoint::tx_status)
     }
     /// get_tx_raw Returns the raw transaction as binary data.
     ///
@@ -364,18 +765,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_raw("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx_raw("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_tx_raw(&self, txid: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/raw", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.bytes()?.to_vec();
-        Ok(resp)
+    pub fn get_tx_raw(&self, txid: &str) -> Result<Vec<u8>, Error> {
+        self.call(endpoint::tx_raw(txid))
     }
     /// get_tx_hex Returns the raw transaction in hex
     ///
@@ -383,19 +783,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_hex("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx_hex("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_tx_hex(&self, txid: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/raw", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.text()?;
-        Ok(resp)
+    pub fn get_tx_hex(&self, txid: &str) -> Result<String, Error> {
+        self.call(endpoint::tx_hex(txid))
     }
     /// get_tx_merkleblock_proof Returns a merkle inclusion proof for the transaction using bitcoind's merkleblock format.
     /// Note: This endpoint is not currently available for Liquid/Elements-based chains.
@@ -403,21 +801,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_merkleblock_proof("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx_merkleblock_proof("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_tx_merkleblock_proof(
         &self,
         txid: &str,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/merkleblock-proof", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.text()?;
-        Ok(resp)
+    ) -> Result<String, Error> {
+        self.call(endpoint::tx_merkleblock_proof(txid))
     }
     /// get_tx_merkle_proof Returns a merkle inclusion proof for the transaction using Electrum's blockchain.transaction.get_merkle format.
     ///
@@ -425,21 +822,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_merkle_proof("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24").unwrap();
+    ///     let response = client.get_tx_merkle_proof("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_tx_merkle_proof(
         &self,
         txid: &str,
-    ) -> Result<MerkleProofFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/merkle-proof", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<MerkleProofFormat, Error> {
+        self.call(endpoint::tx_merkle_proof(txid))
     }
     /// get_tx_outspend Returns the spending status of a transaction output.
     /// Available fields: spent (boolean), txid (optional), vin (optional) and status (optional, the status of the spending tx).
@@ -448,26 +844,21 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_outspend("fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316",Some(1)).unwrap();
+    ///     let response = client.get_tx_outspend("fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316",Some(1))?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_tx_outspend(
         &self,
         txid: &str,
         vout: Option<i32>,
-    ) -> Result<OutspentFormat, Box<dyn std::error::Error>> {
-        let request_url = if let Some(vout_idx) = vout {
-            format!("{}/tx/{}/outspend/{}", self.url, txid, vout_idx)
-        } else {
-            format!("{}/tx/{}/outspend", self.url, txid) // FIXME: not sure if this exist
-        };
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<OutspentFormat, Error> {
+        self.call(endpoint::tx_outspend(txid, vout))
     }
     /// get_tx_outspends Returns the spending status of all transaction outputs.
     ///
@@ -475,37 +866,48 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_tx_outspends("fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316").unwrap();
+    ///     let response = client.get_tx_outspends("fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_tx_outspends(
         &self,
         txid: &str,
-    ) -> Result<Vec<OutspentFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx/{}/outspends", self.url, txid);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<OutspentFormat>, Error> {
+        self.call(endpoint::tx_outspends(txid))
+    }
+    /// get_outspends_many Returns the spending status of all the outputs of the transactions of txids, sending up to `batch_concurrency` requests at once.
+    /// Returns one result per item in input order, like [get_tx_outspends](ApiClient::get_tx_outspends): a missing item does not fail the others.
+    ///
+    /// Route : GET /tx/:txid/outspends for each item
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let responses = client.get_outspends_many(&["d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc"]);
+    ///     println!("{:?}",responses);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn get_outspends_many<S: AsRef<str> + Sync>(&self, txids: &[S]) -> Vec<Result<Vec<OutspentFormat>, Error>> {
+        self.call_many(txids, endpoint::tx_outspends)
     }
     /// post_tx Broadcast a raw transaction to the network.
     /// The transaction should be provided as hex in the request body. The txid will be returned on success.
+    /// A transaction refused by bitcoind is reported as `Error::BroadcastRejected` with the reject code and message.
     ///
     /// Route : POST /tx
     ///
-    pub fn post_tx(&self, hex_transaction: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/tx", self.url);
-        let resp = self
-            .reqwest
-            .post(&request_url)
-            .body(hex_transaction.to_string())
-            .send()?
-            .text()?;
-        Ok(resp)
+    pub fn post_tx(&self, hex_transaction: &str) -> Result<String, Error> {
+        self.call(endpoint::post_tx(hex_transaction))
     }
     /// get_address Get information about an address
     /// Available fields: address/scripthash, chain_stats and mempool_stats.
@@ -516,21 +918,39 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5").unwrap();
+    ///     let response = client.get_address("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address(
         &self,
         address: &str,
-    ) -> Result<AddressInfoFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/address/{}", self.url, address);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<AddressInfoFormat, Error> {
+        self.call(endpoint::address(address))
+    }
+    /// get_addresses_many Get information about the addresses, sending up to `batch_concurrency` requests at once.
+    /// Returns one result per item in input order, like [get_address](ApiClient::get_address): a missing item does not fail the others.
+    ///
+    /// Route : GET /address/:address for each item
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let responses = client.get_addresses_many(&["2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5", "n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk"]);
+    ///     println!("{:?}",responses);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn get_addresses_many<S: AsRef<str> + Sync>(&self, addresses: &[S]) -> Vec<Result<AddressInfoFormat, Error>> {
+        self.call_many(addresses, endpoint::address)
     }
     /// get_script_hash Get information about an scripthash
     /// Available fields: scripthash, chain_stats and mempool_stats.
@@ -541,21 +961,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_script_hash("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c").unwrap();
+    ///     let response = client.get_script_hash("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_script_hash(
         &self,
         scripthash: &str,
-    ) -> Result<AddressInfoFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/scripthash/{}", self.url, scripthash);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<AddressInfoFormat, Error> {
+        self.call(endpoint::scripthash(scripthash))
     }
     /// get_address_txs Get transaction history for the specified address/scripthash, sorted with newest first.
     /// Returns up to 50 mempool transactions plus the first 25 confirmed transactions. You can request more confirmed transactions using :last_seen_txid(see below).
@@ -564,22 +983,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address_txs("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5").unwrap();
+    ///     let response = client.get_address_txs("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address_txs(
         &self,
         address: &str,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/address/{}/txs", self.url,  address);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::address_txs(address))
     }
     /// get_script_hash_txs Get transaction history for the specified address/scripthash, sorted with newest first.
     /// Returns up to 50 mempool transactions plus the first 25 confirmed transactions. You can request more confirmed transactions using :last_seen_txid(see below).
@@ -588,21 +1005,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_script_hash_txs("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c").unwrap();
+    ///     let response = client.get_script_hash_txs("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_script_hash_txs(
         &self,
         scripthash: &str,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/scripthash/{}/txs", self.url, scripthash);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::scripthash_txs(scripthash))
     }
     /// get_address_txs_chain Get confirmed transaction history for the specified address/scripthash, sorted with newest first.
     /// Returns 25 transactions per page. More can be requested by specifying the last txid seen by the previous query.
@@ -611,26 +1027,21 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address_txs_chain("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk",Some("d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc")).unwrap();
+    ///     let response = client.get_address_txs_chain("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk",Some("d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc"))?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address_txs_chain(
         &self,
         address: &str,
         txid: Option<&str>,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = if let Some(id) = txid {
-            format!("{}/address/{}/txs/chain/{}", self.url, address, id)
-        } else {
-            format!("{}/address/{}/txs/chain", self.url, address)
-        };
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::address_txs_chain(address, txid))
     }
     /// get_script_hash_txs_chain Get confirmed transaction history for the specified address/scripthash, sorted with newest first.
     /// Returns 25 transactions per page. More can be requested by specifying the last txid seen by the previous query.
@@ -639,26 +1050,21 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_script_hash_txs_chain("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c",None).unwrap();
+    ///     let response = client.get_script_hash_txs_chain("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c",None)?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_script_hash_txs_chain(
         &self,
         scripthash: &str,
         txid: Option<&str>,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = if let Some(id) = txid {
-            format!("{}/scripthash/{}/txs/chain/{}", self.url, scripthash, id)
-        } else {
-            format!("{}/scripthash/{}/txs/chain", self.url, scripthash)
-        };
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::scripthash_txs_chain(scripthash, txid))
     }
     /// get_address_txs_mempool Get unconfirmed transaction history for the specified address.
     /// Returns up to 50 transactions (no paging).
@@ -667,21 +1073,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address_txs_mempool("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5").unwrap();
+    ///     let response = client.get_address_txs_mempool("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address_txs_mempool(
         &self,
         address: &str,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/address/{}/txs/mempool", self.url, address);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::address_txs_mempool(address))
     }
     /// get_script_hash_txs_mempool Get unconfirmed transaction history for the specified scripthash.
     /// Returns up to 50 transactions (no paging).
@@ -690,24 +1095,63 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_script_hash_txs_mempool("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c").unwrap();
+    ///     let response = client.get_script_hash_txs_mempool("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_script_hash_txs_mempool(
         &self,
         scripthash: &str,
-    ) -> Result<Vec<TransactionFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!(
-            "{}/scripthash/{}/txs/mempool",
-            self.url, scripthash
-        );
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::scripthash_txs_mempool(scripthash))
+    }
+
+    /// address_history Iterator over the full transaction history of the address: mempool transactions,
+    /// then every confirmed transaction newest first, fetched page by page as the iterator is consumed.
+    /// See [TxHistory](TxHistory) to stop at a known txid or below a height.
+    ///
+    /// Route : GET /address/:address/txs/mempool then GET /address/:address/txs/chain[/:last_seen_txid]
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let txs = client.address_history("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5").min_height(1_900_000).collect::<Result<Vec<_>, _>>()?;
+    ///     println!("{:?}",txs);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn address_history(&self, address: &str) -> TxHistory {
+        TxHistory::new(self.clone(), History::new(Target::Address(address.to_string())))
+    }
+
+    /// script_hash_history Iterator over the full transaction history of the scripthash: mempool transactions,
+    /// then every confirmed transaction newest first, fetched page by page as the iterator is consumed.
+    /// See [TxHistory](TxHistory) to stop at a known txid or below a height.
+    ///
+    /// Route : GET /scripthash/:hash/txs/mempool then GET /scripthash/:hash/txs/chain[/:last_seen_txid]
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     for tx in client.script_hash_history("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c") {
+    ///         println!("{:?}",tx?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn script_hash_history(&self, scripthash: &str) -> TxHistory {
+        TxHistory::new(self.clone(), History::new(Target::ScriptHash(scripthash.to_string())))
     }
     /// get_address_utxo Get the list of unspent transaction outputs associated with the address
     /// Available fields: txid, vout, value and status (with the status of the funding tx).
@@ -717,21 +1161,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address_utxo("2NDcM3CGUTwqFL7y8BSBJTYJ9kToeXawkUF").unwrap();
+    ///     let response = client.get_address_utxo("2NDcM3CGUTwqFL7y8BSBJTYJ9kToeXawkUF")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address_utxo(
         &self,
         address: &str,
-    ) -> Result<Vec<UtxoFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/address/{}/utxo", self.url, address);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<UtxoFormat>, Error> {
+        self.call(endpoint::address_utxo(address))
     }
     /// get_script_hash_utxo Get the list of unspent transaction outputs associated with the address
     /// Available fields: txid, vout, value and status (with the status of the funding tx).
@@ -741,21 +1184,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_script_hash_utxo("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c").unwrap();
+    ///     let response = client.get_script_hash_utxo("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_script_hash_utxo(
         &self,
         scripthash: &str,
-    ) -> Result<Vec<UtxoFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/scripthash/{}/utxo", self.url, scripthash);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<UtxoFormat>, Error> {
+        self.call(endpoint::scripthash_utxo(scripthash))
     }
     /// get_address_prefix  This feature is disabled by default on custom api Search for addresses beginning with :prefix.
     /// Returns a JSON array with up to 10 results.
@@ -764,21 +1206,20 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_address_prefix("2NDcM").unwrap();
+    ///     let response = client.get_address_prefix("2NDcM")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     pub fn get_address_prefix(
         &self,
         prefix: &str,
-    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/address-prefix/{}", self.url, prefix);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    ) -> Result<Vec<String>, Error> {
+        self.call(endpoint::address_prefix(prefix))
     }
     /// get_mempool Get mempool backlog statistics. Returns an object with:
     ///     count: the number of transactions in the mempool
@@ -791,12 +1232,13 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_mempool().unwrap();
+    ///     let response = client.get_mempool()?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
     /// Example output:
@@ -809,10 +1251,8 @@ impl ApiClient {
     /// }
     /// ````
     /// In this example, there are transactions weighting a total of 102,131 vbytes that are paying more than 53 sat/vB, 110,990 vbytes of transactions paying between 38 and 53 sat/vB, 138,976 vbytes paying between 34 and 38, etc.
-    pub fn get_mempool(&self) -> Result<MemPoolFormat, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/mempool", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_mempool(&self) -> Result<MemPoolFormat, Error> {
+        self.call(endpoint::mempool())
     }
     /// get_mempool_txids Get the full list of txids in the mempool as an array.
     /// The order of the txids is arbitrary and does not match bitcoind's.
@@ -821,18 +1261,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_mempool_txids().unwrap();
+    ///     let response = client.get_mempool_txids()?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_mempool_txids(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/mempool/txids", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_mempool_txids(&self) -> Result<Vec<String>, Error> {
+        self.call(endpoint::mempool_txids())
     }
     /// get_mempool_recent  Get a list of the last 10 transactions to enter the mempool. Each transaction object contains simplified overview data, with the following fields: txid, fee, vsize and value
     /// Fee estimates
@@ -842,18 +1281,17 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.get_mempool_recent().unwrap();
+    ///     let response = client.get_mempool_recent()?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn get_mempool_recent(&self) -> Result<Vec<MempoolTxFormat>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/mempool/recent", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    pub fn get_mempool_recent(&self) -> Result<Vec<MempoolTxFormat>, Error> {
+        self.call(endpoint::mempool_recent())
     }
     /// fee_estimate Get an object where the key is the confirmation target (in number of blocks) and the value is the estimated feerate (in sat/vB).
     /// The available confirmation targets are 1-25, 144, 504 and 1008 blocks.
@@ -863,17 +1301,371 @@ impl ApiClient {
     ///
     /// Example :
     /// ````rust
-    /// 
-    /// fn main(){
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
     ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-    ///     let response = client.fee_estimate().unwrap();
+    ///     let response = client.fee_estimate()?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    pub fn fee_estimate(&self) -> Result<HashMap<String, f32>, Error> {
+        self.call(endpoint::fee_estimates())
+    }
+    /// get_asset Get information about an issued asset. The native asset also reports peg-in/peg-out statistics.
+    /// Registered assets include their registry metadata (contract, entity, precision, name and ticker).
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_asset(&self, asset_id: &str) -> Result<AssetFormat, Error> {
+        self.call(endpoint::asset(asset_id))
+    }
+    /// get_asset_txs Get transactions associated with the specified asset, sorted with newest first.
+    /// Returns up to 50 mempool transactions plus the first 25 confirmed transactions.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id/txs
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset_txs("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_asset_txs(&self, asset_id: &str) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::asset_txs(asset_id))
+    }
+    /// get_asset_txs_mempool Get unconfirmed transactions associated with the specified asset.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id/txs/mempool
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset_txs_mempool("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")?;
     ///     println!("{:?}",response);
-    ///     
+    ///     Ok(())
     /// }
     /// ````
-    pub fn fee_estimate(&self) -> Result<HashMap<String, f32>, Box<dyn std::error::Error>> {
-        let request_url = format!("{}/fee-estimates", self.url);
-        let resp = self.reqwest.get(&request_url).send()?.json()?;
-        Ok(resp)
+    #[cfg(feature = "liquid")]
+    pub fn get_asset_txs_mempool(&self, asset_id: &str) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::asset_txs_mempool(asset_id))
+    }
+    /// get_asset_txs_chain Get confirmed transactions associated with the specified asset, sorted with newest first.
+    /// Returns 25 transactions per page. More can be requested by specifying the last txid seen by the previous query.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id/txs/chain[/:last_seen_txid]
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset_txs_chain("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d", None)?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_asset_txs_chain(
+        &self,
+        asset_id: &str,
+        txid: Option<&str>,
+    ) -> Result<Vec<TransactionFormat>, Error> {
+        self.call(endpoint::asset_txs_chain(asset_id, txid))
+    }
+    /// get_asset_supply Get the current total supply of the specified asset, in base units.
+    /// For the native asset (L-BTC), this is the total pegged-in amount minus the pegged-out and burned amounts.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id/supply
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset_supply("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_asset_supply(&self, asset_id: &str) -> Result<u64, Error> {
+        self.call(endpoint::asset_supply(asset_id))
+    }
+    /// get_asset_supply_decimal Get the current total supply of the specified asset as a decimal string, using the asset precision.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /asset/:asset_id/supply/decimal
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_asset_supply_decimal("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_asset_supply_decimal(&self, asset_id: &str) -> Result<String, Error> {
+        self.call(endpoint::asset_supply_decimal(asset_id))
+    }
+    /// get_assets_registry Get the list of issued assets registered in the asset registry.
+    /// start_index defaults to 0, limit defaults to 25 (max 100), sort_field is one of name, ticker or domain, sort_dir is asc or desc.
+    /// Requires the `liquid` feature.
+    ///
+    /// Route : GET /assets/registry
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/liquid/api/", None).unwrap();
+    ///     let response = client.get_assets_registry(None, Some(10), Some("ticker"), Some("asc"))?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "liquid")]
+    pub fn get_assets_registry(
+        &self,
+        start_index: Option<u32>,
+        limit: Option<u32>,
+        sort_field: Option<&str>,
+        sort_dir: Option<&str>,
+    ) -> Result<Vec<AssetFormat>, Error> {
+        self.call(endpoint::assets_registry(start_index, limit, sort_field, sort_dir))
+    }
+    /// get_tx_decoded Returns the transaction decoded from its raw binary representation.
+    /// Requires the `bitcoin` feature.
+    ///
+    /// Route : GET /tx/:txid/raw
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let response = client.get_tx_decoded("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "bitcoin")]
+    pub fn get_tx_decoded(&self, txid: &str) -> Result<bitcoin::Transaction, Error> {
+        let raw = self.get_tx_raw(txid)?;
+        consensus_decode(&raw)
+    }
+    /// get_block_decoded Returns the block decoded from its raw binary representation.
+    /// Requires the `bitcoin` feature.
+    ///
+    /// Route : GET /block/:hash/raw
+    ///
+    /// The response from this endpoint can be cached indefinitely.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api;
+    ///
+    /// fn run() -> Result<(), esplora_api::Error> {
+    ///     let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
+    ///     let response = client.get_block_decoded("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")?;
+    ///     println!("{:?}",response);
+    ///     Ok(())
+    /// }
+    /// ````
+    #[cfg(feature = "bitcoin")]
+    pub fn get_block_decoded(&self, hash: &str) -> Result<bitcoin::Block, Error> {
+        let raw = self.get_block_raw_format(hash)?;
+        consensus_decode(&raw)
+    }
+    /// post_bitcoin_tx Broadcast a transaction to the network and return its txid.
+    /// Requires the `bitcoin` feature.
+    ///
+    /// Route : POST /tx
+    ///
+    #[cfg(feature = "bitcoin")]
+    pub fn post_bitcoin_tx(&self, transaction: &bitcoin::Transaction) -> Result<bitcoin::Txid, Error> {
+        let txid = self.post_tx(&bitcoin::consensus::encode::serialize_hex(transaction))?;
+        parse_txid(&txid)
+    }
+    /// Send the endpoint request through the transport, or take its response from the caches, and decode it.
+    pub(crate) fn call<T>(&self, endpoint: Endpoint<T>) -> Result<T, Error> {
+        if let Some(cache) = &self.cache {
+            let cached = match cache.get(&endpoint) {
+                Some(response) if self.still_confirmed(&endpoint, &response) => Some(response),
+                _ => None,
+            };
+            cache.record(&endpoint, cached.is_some());
+            if let Some(response) = cached {
+                return endpoint.parse(&endpoint.request(&self.url, self.timeout), response);
+            }
+        }
+        if let Some(response) = self.store.as_ref().and_then(|store| store.get(&endpoint)) {
+            if self.still_confirmed(&endpoint, &response) {
+                if let Some(cache) = &self.cache {
+                    cache.insert(&endpoint, &response);
+                }
+                return endpoint.parse(&endpoint.request(&self.url, self.timeout), response);
+            }
+        }
+        let (request, response, url) = self.fetch(&endpoint)?;
+        if let Some(served) = &self.served {
+            *served.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(url.to_string());
+        }
+        match endpoint.block_status(&response) {
+            Some((hash, true)) => self.best_chain.insert(hash),
+            Some((hash, false)) => {
+                let _ = self.invalidate_block(hash);
+            }
+            None => {}
+        }
+        if let Some(cache) = &self.cache {
+            cache.insert(&endpoint, &response);
+        }
+        if let Some(store) = &self.store {
+            store.observe(&endpoint, &response);
+        }
+        endpoint.parse(&request, response)
+    }
+    /// Whether the block confirming a cached transaction is still in the best chain, invalidating it otherwise.
+    /// The status of a block is fetched at most once per cache ttl. Other cached responses do not change
+    /// when their block leaves the best chain.
+    fn still_confirmed<T>(&self, endpoint: &Endpoint<T>, response: &Response) -> bool {
+        let hash = match endpoint.confirming_block(response) {
+            Some(hash) => hash,
+            None => return true,
+        };
+        if self.best_chain.contains(&hash) {
+            return true;
+        }
+        match self.fetch(&endpoint::block_status(&hash)) {
+            Ok((_, status, _)) if status.is_success() && endpoint::in_best_chain(&status) => {
+                self.best_chain.insert(&hash);
+                true
+            }
+            Ok((_, status, _)) if status.is_success() => {
+                let _ = self.invalidate_block(&hash);
+                false
+            }
+            _ => false,
+        }
+    }
+    /// Send the endpoint request, through the failover endpoints if any, returning the url that served it.
+    fn fetch<T>(&self, endpoint: &Endpoint<T>) -> Result<(Request, Response, &str), Error> {
+        match &self.endpoints {
+            Some(endpoints) => self.send_failover(endpoint, endpoints),
+            None => {
+                let request = endpoint.request(&self.url, self.timeout);
+                let response = self.send(&request)?;
+                Ok((request, response, self.url.as_str()))
+            }
+        }
+    }
+    /// Call the endpoint of each key on up to batch_concurrency scoped threads, returning results in key order.
+    fn call_many<S: AsRef<str> + Sync, T: Send>(&self, keys: &[S], endpoint: fn(&str) -> Endpoint<T>) -> Vec<Result<T, Error>> {
+        let workers = self.batch_concurrency.min(keys.len());
+        if workers <= 1 {
+            return keys.iter().map(|key| self.call(endpoint(key.as_ref()))).collect();
+        }
+        let next = AtomicUsize::new(0);
+        let worker = || {
+            let mut results = Vec::new();
+            loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                match keys.get(index) {
+                    Some(key) => results.push((index, self.call(endpoint(key.as_ref())))),
+                    None => return results,
+                }
+            }
+        };
+        let mut results: Vec<(usize, Result<T, Error>)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(worker)).collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+    /// Send the endpoint request to the first available endpoint, probing dead endpoints due for a retry.
+    fn send_failover<'a, T>(
+        &self,
+        endpoint: &Endpoint<T>,
+        endpoints: &'a Endpoints,
+    ) -> Result<(Request, Response, &'a str), Error> {
+        let mut failure = None;
+        for candidate in endpoints.candidates() {
+            if candidate.probe {
+                let probe = endpoint::blocks_tip_height().request(candidate.url, self.timeout);
+                let outcome = self.send(&probe);
+                if failover::is_unavailable(&outcome) {
+                    endpoints.mark_failure(candidate.index);
+                    failure = Some(failover::unavailable_error(outcome));
+                    continue;
+                }
+                endpoints.mark_alive(candidate.index);
+            }
+            let request = endpoint.request(candidate.url, self.timeout);
+            let outcome = self.send(&request);
+            if failover::is_unavailable(&outcome) {
+                endpoints.mark_failure(candidate.index);
+                failure = Some(failover::unavailable_error(outcome));
+                continue;
+            }
+            endpoints.mark_success(candidate.index);
+            return Ok((request, outcome?, candidate.url));
+        }
+        Err(failure.unwrap_or_else(|| Error::InvalidInput("failover client has no endpoint".to_string())))
+    }
+    /// Send request, waiting for the rate limiter and retrying transient failures when a retry policy is set.
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                std::thread::sleep(rate_limiter.reserve());
+            }
+            let outcome = self.transport.send(request);
+            let delay = self.retry.as_ref().and_then(|retry| retry.retry_delay(request, &outcome, attempt));
+            match delay {
+                Some(delay) => std::thread::sleep(delay),
+                None => return outcome,
+            }
+            attempt += 1;
+        }
     }
 }
@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::ops::RangeBounds;
+use std::thread;
+use std::time::Duration;
+
+use crate::blocks::{check_txs_page, BlockRange, Step, BLOCK_TXS_PAGE_SIZE};
+use crate::data::blockstream::{BlockFormat, TransactionFormat};
+use crate::endpoint;
+use crate::error::Error;
+use crate::history::History;
+use super::ApiClient;
+
+/// Iterator over the full transaction history of an address or scripthash,
+/// returned by [ApiClient::address_history](ApiClient::address_history) and
+/// [ApiClient::script_hash_history](ApiClient::script_hash_history).
+///
+/// Yields mempool transactions first, then confirmed transactions newest first, fetching pages as it is iterated.
+/// The iterator ends after the first error.
+#[derive(Debug)]
+pub struct TxHistory {
+    client: ApiClient,
+    history: History,
+}
+
+impl TxHistory {
+    pub(crate) fn new(client: ApiClient, history: History) -> Self {
+        TxHistory { client, history }
+    }
+    /// Only walk confirmed transactions.
+    pub fn skip_mempool(mut self) -> Self {
+        self.history.skip_mempool();
+        self
+    }
+    /// End the iterator at txid, which is not yielded, e.g. the newest transaction already known.
+    pub fn stop_at_txid(mut self, txid: impl Into<String>) -> Self {
+        self.history.stop_at_txid(txid.into());
+        self
+    }
+    /// End the iterator at the first transaction confirmed below height.
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.history.min_height(height);
+        self
+    }
+}
+
+impl Iterator for TxHistory {
+    type Item = Result<TransactionFormat, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(tx) = self.history.pop() {
+                return Some(Ok(tx));
+            }
+            let endpoint = self.history.next_page()?;
+            match self.client.call(endpoint) {
+                Ok(page) => self.history.push_page(page),
+                Err(err) => {
+                    self.history.finish();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// Iterator over every transaction of a block, in block order, returned by
+/// [ApiClient::block_transactions](ApiClient::block_transactions).
+///
+/// Fetches the block to learn its `tx_count`, then its pages of 25 transactions, checking each page is complete.
+/// The iterator ends after the first error.
+#[derive(Debug)]
+pub struct BlockTransactions {
+    client: ApiClient,
+    hash: String,
+    concurrency: usize,
+    tx_count: Option<u32>,
+    next_start: u32,
+    buffer: VecDeque<TransactionFormat>,
+    /// Error of a page, returned once the pages before it are yielded.
+    error: Option<Error>,
+    done: bool,
+}
+
+impl BlockTransactions {
+    pub(crate) fn new(client: ApiClient, hash: &str) -> Self {
+        BlockTransactions {
+            client,
+            hash: hash.to_string(),
+            concurrency: 1,
+            tx_count: None,
+            next_start: 0,
+            buffer: VecDeque::new(),
+            error: None,
+            done: false,
+        }
+    }
+    /// Fetch up to concurrency pages at once on scoped threads, 1 by default.
+    /// Transactions are still yielded in block order.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    /// Fetch the next pages into the buffer, marking the iterator done after the last one.
+    /// Pages fetched after a failed one are dropped.
+    fn fetch_pages(&mut self) -> Result<(), Error> {
+        let tx_count = match self.tx_count {
+            Some(tx_count) => tx_count,
+            None => *self.tx_count.insert(self.client.call(endpoint::block(&self.hash))?.tx_count),
+        };
+        let starts: Vec<u32> = (self.next_start..tx_count)
+            .step_by(BLOCK_TXS_PAGE_SIZE as usize)
+            .take(self.concurrency)
+            .collect();
+        let (client, hash) = (&self.client, self.hash.as_str());
+        let fetch = |start: u32| client.call(endpoint::block_txs(hash, Some(start as i32)));
+        let pages: Vec<Result<Vec<TransactionFormat>, Error>> = match starts.as_slice() {
+            [start] => vec![fetch(*start)],
+            _ => thread::scope(|scope| {
+                let fetch = &fetch;
+                let handles: Vec<_> = starts.iter().map(|start| scope.spawn(move || fetch(*start))).collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                    .collect()
+            }),
+        };
+        for (start, page) in starts.iter().zip(pages) {
+            self.buffer.extend(check_txs_page(hash, tx_count, *start, page?)?);
+        }
+        self.next_start = starts.last().map_or(tx_count, |start| start + BLOCK_TXS_PAGE_SIZE);
+        self.done = self.next_start >= tx_count;
+        Ok(())
+    }
+}
+
+impl Iterator for BlockTransactions {
+    type Item = Result<TransactionFormat, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(tx) = self.buffer.pop_front() {
+                return Some(Ok(tx));
+            }
+            if let Some(err) = self.error.take() {
+                self.done = true;
+                return Some(Err(err));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.fetch_pages() {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+/// Iterator over a range of blocks in ascending height order, returned by
+/// [ApiClient::blocks_range](ApiClient::blocks_range).
+///
+/// Blocks are fetched 10 at a time and must each link to the previous one through `previousblockhash`,
+/// a broken link (e.g. a reorg while walking) ends the iterator with `Error::Inconsistent`
+/// after checking the status of the previous block, which drops it from the client caches if it left the best chain.
+/// Heights above the tip are awaited, so an unbounded range follows the tip indefinitely.
+/// The iterator ends after the first error.
+#[derive(Debug)]
+pub struct BlocksRange {
+    client: ApiClient,
+    range: BlockRange,
+}
+
+impl BlocksRange {
+    pub(crate) fn new(client: ApiClient, range: impl RangeBounds<u32>) -> Self {
+        BlocksRange {
+            client,
+            range: BlockRange::new(range),
+        }
+    }
+    /// Time between two checks of the tip while waiting for the next block, 30s by default.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.range.poll_interval(poll_interval);
+        self
+    }
+    /// Run step, None when the walk is over.
+    fn advance(&mut self) -> Result<Option<BlockFormat>, Error> {
+        loop {
+            match self.range.step()? {
+                Step::Block(block) => return Ok(Some(block)),
+                Step::Done => return Ok(None),
+                Step::Tip => {
+                    let height = self.client.call(endpoint::blocks_tip_height())?;
+                    self.range.push_tip(height);
+                }
+                Step::Page(endpoint) => {
+                    let page = self.client.call(endpoint)?;
+                    self.range.push_page(page)?;
+                }
+                Step::Wait(delay) => thread::sleep(delay),
+                Step::Unlinked(status, err) => {
+                    let _ = self.client.call(status);
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for BlocksRange {
+    type Item = Result<BlockFormat, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.advance() {
+            Ok(block) => block.map(Ok),
+            Err(err) => {
+                self.range.finish();
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::thread;
+
+use serde::Serialize;
+
+use crate::data::blockstream::{TransactionFormat, TxStatusFormat, UtxoFormat};
+use crate::error::Error;
+use crate::quorum::{check_required, tally};
+use super::ApiClient;
+
+/// Client sending each call to several independent Esplora servers and accepting the result
+/// only when at least `required` of them returned the same value.
+///
+/// Calls are sent to all servers concurrently, one thread per server. A value returned by too few servers,
+/// or two different values both returned by `required` servers, is reported as `Error::QuorumNotReached`,
+/// listing what each server returned.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::blocking::{ApiClient, QuorumClient};
+///
+/// fn run() -> Result<(), esplora_api::Error> {
+///     let quorum = QuorumClient::new(vec![
+///         ApiClient::new("https://blockstream.info/testnet/api", None)?,
+///         ApiClient::new("https://mempool.space/testnet/api", None)?,
+///         ApiClient::new("https://electrs.local/api", None)?,
+///     ], 2)?;
+///     let status = quorum.get_tx_status("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")?;
+///     println!("{:?}", status);
+///     Ok(())
+/// }
+/// ````
+#[derive(Debug, Clone)]
+pub struct QuorumClient {
+    clients: Vec<ApiClient>,
+    required: usize,
+}
+
+impl QuorumClient {
+    /// new quorum of required servers among clients. Fails unless 1 <= required <= clients.len().
+    pub fn new(clients: Vec<ApiClient>, required: usize) -> Result<Self, Error> {
+        check_required(required, clients.len())?;
+        Ok(QuorumClient { clients, required })
+    }
+    /// Clients of the quorum servers.
+    pub fn clients(&self) -> &[ApiClient] {
+        &self.clients
+    }
+    /// Number of servers that must agree.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+    /// query send call to every server and return the value returned by at least `required` of them.
+    ///
+    /// Example :
+    /// ````rust
+    /// # fn run(quorum: esplora_api::blocking::QuorumClient) -> Result<(), esplora_api::Error> {
+    /// let outspends = quorum.query(|client| client.get_tx_outspends("txid"))?;
+    /// # Ok(())
+    /// # }
+    /// ````
+    pub fn query<T, F>(&self, call: F) -> Result<T, Error>
+    where
+        F: Fn(&ApiClient) -> Result<T, Error> + Sync,
+        T: PartialEq + Serialize + Send,
+    {
+        let call = &call;
+        let results: Vec<Result<T, Error>> = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .clients
+                .iter()
+                .map(|client| scope.spawn(move || call(client)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+        let urls = self.clients.iter().map(|client| client.url.clone());
+        tally(self.required, urls.zip(results).collect())
+    }
+    /// get_tx_status Returns the transaction confirmation status agreed by the quorum.
+    ///
+    /// Route : GET /tx/:txid/status
+    pub fn get_tx_status(&self, txid: &str) -> Result<TxStatusFormat, Error> {
+        self.query(|client| client.get_tx_status(txid))
+    }
+    /// get_tx Returns information about the transaction agreed by the quorum.
+    ///
+    /// Route : GET /tx/:txid
+    pub fn get_tx(&self, txid: &str) -> Result<TransactionFormat, Error> {
+        self.query(|client| client.get_tx(txid))
+    }
+    /// get_address_utxo Returns the unspent transaction outputs of the address agreed by the quorum,
+    /// sorted by txid and vout since servers may list them in different orders.
+    ///
+    /// Route : GET /address/:address/utxo
+    pub fn get_address_utxo(&self, address: &str) -> Result<Vec<UtxoFormat>, Error> {
+        self.query(|client| {
+            let mut utxos = client.get_address_utxo(address)?;
+            utxos.sort_by(|a, b| (&a.txid, a.vout).cmp(&(&b.txid, b.vout)));
+            Ok(utxos)
+        })
+    }
+    /// get_blocks_tip_hash Returns the hash of the last block agreed by the quorum.
+    ///
+    /// Route : GET /blocks/tip/hash
+    pub fn get_blocks_tip_hash(&self) -> Result<String, Error> {
+        self.query(|client| client.get_blocks_tip_hash())
+    }
+    /// get_blocks_tip_height Returns the height of the last block agreed by the quorum.
+    ///
+    /// Route : GET /blocks/tip/height
+    pub fn get_blocks_tip_height(&self) -> Result<i32, Error> {
+        self.query(|client| client.get_blocks_tip_height())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::Error;
+use crate::transport::{Request, Response};
+
+/// HTTP layer used by the blocking [ApiClient](crate::blocking::ApiClient).
+///
+/// Implemented for `reqwest::blocking::Client`. Implement it to use another HTTP client or to serve canned responses in tests.
+/// Non-2xx responses must be returned as `Ok`, the client turns them into `Error::HttpStatus`.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::blocking::{ApiClient, EsploraTransport};
+/// use esplora_api::transport::{Request, Response};
+///
+/// struct Tip;
+///
+/// impl EsploraTransport for Tip {
+///     fn send(&self, _request: &Request) -> Result<Response, esplora_api::Error> {
+///         Ok(Response::new(200, "42"))
+///     }
+/// }
+///
+/// fn main() -> Result<(), esplora_api::Error> {
+///     let client = ApiClient::with_transport("https://blockstream.info/api", Tip)?;
+///     assert_eq!(client.get_blocks_tip_height()?, 42);
+///     Ok(())
+/// }
+/// ````
+pub trait EsploraTransport: Send + Sync {
+    /// Perform the HTTP exchange described by request.
+    fn send(&self, request: &Request) -> Result<Response, Error>;
+}
+
+#[cfg(feature = "reqwest")]
+impl EsploraTransport for reqwest::blocking::Client {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        let mut builder = match request.method {
+            crate::transport::Method::Get => self.get(&request.url),
+            crate::transport::Method::Post => self.post(&request.url),
+        };
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let resp = builder.send()?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (name.as_str().to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+            })
+            .collect();
+        let body = resp.bytes()?.to_vec();
+        Ok(Response { status, headers, body })
+    }
+}
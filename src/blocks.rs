@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+use std::ops::{Bound, RangeBounds};
+use std::time::Duration;
+
+use crate::data::blockstream::{BlockFormat, BlockStatus, TransactionFormat};
+use crate::endpoint::{self, Endpoint};
+use crate::error::Error;
+
+/// Number of transactions returned by `GET /block/:hash/txs/:start_index`.
+pub(crate) const BLOCK_TXS_PAGE_SIZE: u32 = 25;
+
+/// Check that the page of block transactions starting at start holds every transaction expected from tx_count.
+pub(crate) fn check_txs_page(
+    hash: &str,
+    tx_count: u32,
+    start: u32,
+    page: Vec<TransactionFormat>,
+) -> Result<Vec<TransactionFormat>, Error> {
+    let expected = tx_count.saturating_sub(start).min(BLOCK_TXS_PAGE_SIZE) as usize;
+    if page.len() != expected {
+        return Err(Error::Inconsistent(format!(
+            "block {} has {} transactions but the page at index {} holds {} instead of {}",
+            hash,
+            tx_count,
+            start,
+            page.len(),
+            expected
+        )));
+    }
+    Ok(page)
+}
+
+/// Number of blocks returned by `GET /blocks/:start_height`.
+const BLOCKS_PAGE_SIZE: u32 = 10;
+
+/// Next action of a block range walk.
+pub(crate) enum Step {
+    /// Yield this block.
+    Block(BlockFormat),
+    /// Fetch the tip height and give it to `push_tip`.
+    Tip,
+    /// Fetch this page of blocks and give it to `push_page`.
+    Page(Endpoint<Vec<BlockFormat>>),
+    /// Sleep before fetching the tip again.
+    Wait(Duration),
+    /// The next block does not follow the previous one: fetch the status of the previous block,
+    /// so the client caches drop it if it left the best chain, then end with the error.
+    Unlinked(Endpoint<BlockStatus>, Error),
+    Done,
+}
+
+/// State of a walk over a range of blocks in ascending order, shared by the async stream and the blocking iterator.
+///
+/// Blocks are fetched 10 at a time from the highest height of each page. Heights above the tip are awaited,
+/// checking the tip every poll_interval. Every block must link to the previous one through `previousblockhash`.
+#[derive(Debug)]
+pub(crate) struct BlockRange {
+    next: u32,
+    end: Option<u32>,
+    tip: Option<u32>,
+    stale_tip: bool,
+    requested: Option<u32>,
+    previous: Option<String>,
+    buffer: VecDeque<BlockFormat>,
+    poll_interval: Duration,
+    done: bool,
+}
+
+impl BlockRange {
+    pub(crate) fn new(range: impl RangeBounds<u32>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(start) => Some(*start),
+            Bound::Excluded(start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => Some(Some(*end)),
+            Bound::Excluded(end) => end.checked_sub(1).map(Some),
+            Bound::Unbounded => Some(None),
+        };
+        let (next, end, done) = match (start, end) {
+            (Some(start), Some(end)) => (start, end, end.is_some_and(|end| start > end)),
+            _ => (0, None, true),
+        };
+        BlockRange {
+            next,
+            end,
+            tip: None,
+            stale_tip: false,
+            requested: None,
+            previous: None,
+            buffer: VecDeque::new(),
+            poll_interval: Duration::from_secs(30),
+            done,
+        }
+    }
+    pub(crate) fn poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = poll_interval;
+    }
+    /// Next action.
+    pub(crate) fn step(&mut self) -> Result<Step, Error> {
+        if let Some(block) = self.buffer.pop_front() {
+            if let Some(previous) = &self.previous {
+                if block.previousblockhash.as_ref() != Some(previous) {
+                    let error = Error::Inconsistent(format!(
+                        "block {} at height {} does not follow block {}",
+                        block.id, block.height, previous
+                    ));
+                    return Ok(Step::Unlinked(endpoint::block_status(previous), error));
+                }
+            }
+            self.previous = Some(block.id.clone());
+            self.next = block.height + 1;
+            return Ok(Step::Block(block));
+        }
+        if self.done || self.end.is_some_and(|end| self.next > end) {
+            return Ok(Step::Done);
+        }
+        let tip = match self.tip {
+            Some(tip) if !self.stale_tip => tip,
+            _ => return Ok(Step::Tip),
+        };
+        if self.next > tip {
+            self.stale_tip = true;
+            return Ok(Step::Wait(self.poll_interval));
+        }
+        let mut start = tip.min(self.next.saturating_add(BLOCKS_PAGE_SIZE - 1));
+        if let Some(end) = self.end {
+            start = start.min(end);
+        }
+        self.requested = Some(start);
+        Ok(Step::Page(endpoint::blocks(start as i32)))
+    }
+    pub(crate) fn push_tip(&mut self, height: i32) {
+        self.tip = Some(height.max(0) as u32);
+        self.stale_tip = false;
+    }
+    /// Store the page fetched from the endpoint returned by step, checking it holds every requested height.
+    pub(crate) fn push_page(&mut self, page: Vec<BlockFormat>) -> Result<(), Error> {
+        let start = self.requested.take().unwrap_or(self.next);
+        let mut blocks: Vec<BlockFormat> = page
+            .into_iter()
+            .filter(|block| block.height >= self.next && block.height <= start)
+            .collect();
+        blocks.sort_by_key(|block| block.height);
+        let heights_match = blocks.len() as u32 == start + 1 - self.next
+            && blocks.iter().zip(self.next..).all(|(block, height)| block.height == height);
+        if !heights_match {
+            return Err(Error::Inconsistent(format!(
+                "blocks from height {} did not return heights {} to {}",
+                start, self.next, start
+            )));
+        }
+        self.buffer.extend(blocks);
+        Ok(())
+    }
+    /// End the walk, e.g. after an error.
+    pub(crate) fn finish(&mut self) {
+        self.done = true;
+        self.buffer.clear();
+    }
+}
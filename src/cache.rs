@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+
+use crate::endpoint::Endpoint;
+use crate::transport::Response;
+
+/// In-memory cache of Esplora responses, shared by the clones of a client.
+///
+/// Immutable data (blocks by hash, their transactions, raw blocks and confirmed transactions)
+/// is kept until evicted by newer entries, tip, mempool and fee estimates are kept for `ttl`.
+/// Other routes, including transaction and block statuses, are never cached, nor are error responses.
+/// Merkle proofs are not cached either: they do not name their block, so they could not be dropped after a reorg.
+/// Responses tied to a block are dropped when a block status shows the block left the best chain.
+///
+/// Example :
+/// ````rust
+/// use std::time::Duration;
+/// use esplora_api::CachePolicy;
+///
+/// let cache = CachePolicy {
+///     capacity: 10_000,
+///     ttl: Duration::from_secs(5),
+/// };
+/// ````
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Maximum number of responses kept, the least recently used are evicted first.
+    pub capacity: usize,
+    /// Time tip, mempool and fee estimates responses are kept, and time a block seen in the best chain
+    /// is trusted before the cached transactions it confirms are checked again.
+    pub ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    /// 1000 responses, tip and mempool kept 10s.
+    fn default() -> Self {
+        CachePolicy {
+            capacity: 1000,
+            ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Counters of a client cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CacheStats {
+    /// Calls answered from the cache.
+    pub hits: u64,
+    /// Calls to cacheable routes sent to Esplora.
+    pub misses: u64,
+    /// Responses currently cached.
+    pub entries: usize,
+}
+
+/// How long the response of an endpoint can be cached.
+#[derive(Clone, Copy)]
+pub(crate) enum Caching {
+    Never,
+    /// The response never changes.
+    Immutable,
+    /// The response never changes once it satisfies the predicate, e.g. once the transaction is confirmed.
+    ImmutableIf(fn(&Response) -> bool),
+    /// The response changes with the tip or the mempool and is kept for the cache ttl.
+    Volatile,
+}
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    entries: LruCache<String, Entry>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: Response,
+    expires: Option<Instant>,
+    /// Block the response is tied to, dropped by `invalidate`.
+    block: Option<String>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        let capacity = NonZeroUsize::new(policy.capacity).unwrap_or(NonZeroUsize::MIN);
+        ResponseCache {
+            ttl: policy.ttl,
+            state: Mutex::new(State {
+                entries: LruCache::new(capacity),
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+    /// Cached response of the endpoint. Hits and misses are counted by `record`, once the client knows
+    /// whether the response is served.
+    pub(crate) fn get<T>(&self, endpoint: &Endpoint<T>) -> Option<Response> {
+        if let Caching::Never = endpoint.caching() {
+            return None;
+        }
+        let mut state = self.lock();
+        let fresh = match state.entries.get(endpoint.path()) {
+            Some(entry) => entry.expires.is_none_or(|expires| Instant::now() < expires),
+            None => false,
+        };
+        if !fresh {
+            state.entries.pop(endpoint.path());
+            return None;
+        }
+        state.entries.get(endpoint.path()).map(|entry| entry.response.clone())
+    }
+    /// Count a hit when the call was answered from the cache, a miss otherwise, for cacheable endpoints.
+    pub(crate) fn record<T>(&self, endpoint: &Endpoint<T>, hit: bool) {
+        if let Caching::Never = endpoint.caching() {
+            return;
+        }
+        let mut state = self.lock();
+        if hit {
+            state.hits += 1;
+        } else {
+            state.misses += 1;
+        }
+    }
+    /// Keep the response of the endpoint if it is successful and cacheable.
+    pub(crate) fn insert<T>(&self, endpoint: &Endpoint<T>, response: &Response) {
+        if !response.is_success() {
+            return;
+        }
+        let expires = match endpoint.caching() {
+            Caching::Never => return,
+            Caching::Immutable => None,
+            Caching::ImmutableIf(immutable) if immutable(response) => None,
+            Caching::ImmutableIf(_) => return,
+            Caching::Volatile => Some(Instant::now() + self.ttl),
+        };
+        let entry = Entry {
+            response: response.clone(),
+            expires,
+            block: endpoint.block(response),
+        };
+        self.lock().entries.put(endpoint.path().to_string(), entry);
+    }
+    /// Drop every response tied to the block.
+    pub(crate) fn invalidate(&self, hash: &str) {
+        let mut state = self.lock();
+        let keys: Vec<String> = state
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.block.as_deref() == Some(hash))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            state.entries.pop(&key);
+        }
+    }
+    pub(crate) fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Blocks recently seen in the best chain, so cached confirmed transactions are served without checking
+/// the status of their block on every call. A block is checked again once `ttl` elapsed.
+#[derive(Debug)]
+pub(crate) struct BestChainBlocks {
+    ttl: Duration,
+    checked: Mutex<HashMap<String, Instant>>,
+}
+
+impl BestChainBlocks {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        BestChainBlocks {
+            ttl,
+            checked: Mutex::new(HashMap::new()),
+        }
+    }
+    /// Whether the block was seen in the best chain less than ttl ago.
+    pub(crate) fn contains(&self, hash: &str) -> bool {
+        let checked = self.checked.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        checked.get(hash).is_some_and(|checked| checked.elapsed() < self.ttl)
+    }
+    /// Remember the block was just seen in the best chain, forgetting the expired ones.
+    pub(crate) fn insert(&self, hash: &str) {
+        let mut checked = self.checked.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let ttl = self.ttl;
+        checked.retain(|_, checked| checked.elapsed() < ttl);
+        checked.insert(hash.to_string(), Instant::now());
+    }
+    pub(crate) fn remove(&self, hash: &str) {
+        self.checked.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(hash);
+    }
+}
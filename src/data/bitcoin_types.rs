@@ -0,0 +1,158 @@
+//! Typed accessors converting Esplora strings into [rust-bitcoin](https://docs.rs/bitcoin) types.
+//! Available with the `bitcoin` feature.
+use std::str::FromStr;
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::consensus::{deserialize, Decodable};
+use bitcoin::hex::FromHex;
+use bitcoin::{Address, Amount, BlockHash, OutPoint, ScriptBuf, TxMerkleNode, Txid, Witness};
+
+use crate::data::blockstream::{
+    BlockFormat, BlockStatus, MempoolTxFormat, MerkleProofFormat, OutspentFormat,
+    TransactionFormat, TxStatusFormat, UtxoFormat, VinFormat, VoutFormat,
+};
+use crate::error::Error;
+
+fn parse<T>(value: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    T::from_str(value).map_err(|err| Error::Parse(format!("{}: {}", value, err)))
+}
+
+/// Decode raw bytes returned by Esplora with consensus encoding.
+pub(crate) fn consensus_decode<T: Decodable>(raw: &[u8]) -> Result<T, Error> {
+    deserialize(raw).map_err(|err| Error::Parse(err.to_string()))
+}
+
+/// Parse a txid returned by Esplora.
+pub(crate) fn parse_txid(value: &str) -> Result<Txid, Error> {
+    parse(value.trim())
+}
+
+fn parse_script(hex: &str) -> Result<ScriptBuf, Error> {
+    ScriptBuf::from_hex(hex).map_err(|err| Error::Parse(format!("{}: {}", hex, err)))
+}
+
+fn parse_opt<T>(value: &Option<String>) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.as_deref().map(parse).transpose()
+}
+
+impl BlockFormat {
+    /// Block hash (`id` field).
+    pub fn block_hash(&self) -> Result<BlockHash, Error> {
+        parse(&self.id)
+    }
+    /// Previous block hash, None on the genesis block.
+    pub fn prev_block_hash(&self) -> Result<Option<BlockHash>, Error> {
+        parse_opt(&self.previousblockhash)
+    }
+    /// Merkle root of the block transactions.
+    pub fn merkle_root_hash(&self) -> Result<TxMerkleNode, Error> {
+        parse(&self.merkle_root)
+    }
+}
+
+impl BlockStatus {
+    /// Hash of the next block in the best chain, if any.
+    pub fn next_best_hash(&self) -> Result<Option<BlockHash>, Error> {
+        parse_opt(&self.next_best)
+    }
+}
+
+impl VoutFormat {
+    /// Output script.
+    pub fn script_pubkey(&self) -> Result<ScriptBuf, Error> {
+        parse_script(&self.scriptpubkey)
+    }
+    /// Output address. The network is not checked, see [`Address::require_network`].
+    pub fn address(&self) -> Result<Option<Address<NetworkUnchecked>>, Error> {
+        parse_opt(&self.scriptpubkey_address)
+    }
+    /// Output value, None for confidential outputs.
+    pub fn amount(&self) -> Option<Amount> {
+        self.value.map(Amount::from_sat)
+    }
+}
+
+impl VinFormat {
+    /// Outpoint spent by this input.
+    pub fn previous_output(&self) -> Result<OutPoint, Error> {
+        Ok(OutPoint::new(parse(&self.txid)?, self.vout))
+    }
+    /// Input script.
+    pub fn script_sig(&self) -> Result<ScriptBuf, Error> {
+        parse_script(&self.scriptsig)
+    }
+    /// Witness stack, empty for non-SegWit inputs.
+    pub fn witness_stack(&self) -> Result<Witness, Error> {
+        let items = self
+            .witness
+            .iter()
+            .flatten()
+            .map(|item| {
+                Vec::<u8>::from_hex(item).map_err(|err| Error::Parse(format!("{}: {}", item, err)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Witness::from_slice(&items))
+    }
+}
+
+impl TxStatusFormat {
+    /// Hash of the block containing the transaction, None while unconfirmed.
+    pub fn confirmed_block_hash(&self) -> Result<Option<BlockHash>, Error> {
+        parse_opt(&self.block_hash)
+    }
+}
+
+impl UtxoFormat {
+    /// Outpoint of the unspent output.
+    pub fn outpoint(&self) -> Result<OutPoint, Error> {
+        Ok(OutPoint::new(parse(&self.txid)?, self.vout))
+    }
+    /// Output value, None for confidential outputs.
+    pub fn amount(&self) -> Option<Amount> {
+        self.value.map(Amount::from_sat)
+    }
+}
+
+impl TransactionFormat {
+    /// Transaction id.
+    pub fn txid(&self) -> Result<Txid, Error> {
+        parse(&self.txid)
+    }
+    /// Fee paid by the transaction.
+    pub fn fee_amount(&self) -> Amount {
+        Amount::from_sat(self.fee)
+    }
+}
+
+impl MerkleProofFormat {
+    /// Merkle branch of the transaction.
+    pub fn merkle_branch(&self) -> Result<Vec<TxMerkleNode>, Error> {
+        self.merkle.iter().map(|node| parse(node)).collect()
+    }
+}
+
+impl OutspentFormat {
+    /// Id of the spending transaction, if spent.
+    pub fn spending_txid(&self) -> Result<Option<Txid>, Error> {
+        parse_opt(&self.txid)
+    }
+}
+
+impl MempoolTxFormat {
+    /// Transaction id.
+    pub fn txid(&self) -> Result<Txid, Error> {
+        parse(&self.txid)
+    }
+    /// Fee paid by the transaction.
+    pub fn fee_amount(&self) -> Amount {
+        Amount::from_sat(self.fee)
+    }
+}
@@ -1,117 +1,209 @@
 /// blockstream reference all data structures provided by Blocksteam API and documentation.
 /// Official API documentation is available at [Blockstream Esplora API](https://github.com/Blockstream/esplora/blob/master/API.md)
 /// Amounts are always represented in satoshis.
-use serde::Deserialize;
-#[derive(Deserialize, Debug)]
+///
+/// Fields are declared in Esplora order and optional fields follow Esplora omission rules,
+/// so serializing a structure produces the same JSON as the Esplora server.
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "liquid")]
+use crate::data::liquid::{BlockProofFormat, IssuanceFormat, PegoutFormat};
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BlockFormat {
     pub id: String,
     pub height: u32,
     pub version: u32,
     pub timestamp: u32,
-    pub bits: u32,
-    pub nonce: u32,
-    pub difficulty: u32,
-    pub merkle_root: String,
     pub tx_count: u32,
     pub size: u32,
     pub weight: u32,
-    pub previousblockhash: String,
+    pub merkle_root: String,
+    /// None on the genesis block.
+    pub previousblockhash: Option<String>,
+    pub mediantime: u32,
+    /// None on Elements-based chains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u32>,
+    /// None on Elements-based chains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bits: Option<u32>,
+    /// None on Elements-based chains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<f64>,
+    /// Signed block proof of Elements-based chains, served under the `ext` name and also accepted as `proof`.
+    #[cfg(feature = "liquid")]
+    #[serde(default, rename = "ext", alias = "proof", skip_serializing_if = "Option::is_none")]
+    pub proof: Option<BlockProofFormat>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BlockStatus {
     pub in_best_chain: bool,
-    pub next_best: String,
-    pub height: u32,
+    /// Absent on orphaned blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Absent on the tip and on orphaned blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_best: Option<String>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VoutFormat {
     pub scriptpubkey: String,
     pub scriptpubkey_asm: String,
     pub scriptpubkey_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scriptpubkey_address: Option<String>,
-    pub value: u32,
+    /// None for confidential outputs of Elements-based chains, see `valuecommitment`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valuecommitment: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assetcommitment: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noncecommitment: Option<String>,
+    /// Present on outputs pegging out to the parent chain.
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pegout: Option<PegoutFormat>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VinFormat {
     pub txid: String,
     pub vout: u32,
-    pub is_coinbase: bool,
+    /// None for coinbase inputs.
+    pub prevout: Option<VoutFormat>,
     pub scriptsig: String,
     pub scriptsig_asm: String,
-    //FIXME
-    // inner_redeemscript_asm: String,
-    // inner_witnessscript_asm: String,
+    /// Hex encoded witness stack items, absent for non-SegWit inputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Vec<String>>,
+    pub is_coinbase: bool,
     pub sequence: u32,
-    //FIXME
-    // witness[]
-    // #[serde(skip_deserializing)]
-    pub prevout: Option<VoutFormat>,
+    /// Present when spending a P2SH output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_redeemscript_asm: Option<String>,
+    /// Present when spending a P2WSH or P2SH-P2WSH output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_witnessscript_asm: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_pegin: Option<bool>,
+    /// Present on inputs issuing or reissuing an asset.
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuance: Option<IssuanceFormat>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TxStatusFormat {
     pub confirmed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block_height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<String>,
-    pub block_time: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<u32>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UtxoFormat {
     pub txid: String,
-    pub vout: u16,
+    pub vout: u32,
     pub status: TxStatusFormat,
-    pub value: u32,
+    /// None for confidential outputs of Elements-based chains, see `valuecommitment`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valuecommitment: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assetcommitment: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noncecommitment: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surjection_proof: Option<String>,
+    #[cfg(feature = "liquid")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_proof: Option<String>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TransactionFormat {
     pub txid: String,
     pub version: u32,
     pub locktime: u32,
-    pub size: u32,
-    pub weight: u32,
-    pub fee: u32,
     pub vin: Vec<VinFormat>,
     pub vout: Vec<VoutFormat>,
-    pub status: TxStatusFormat,
+    pub size: u32,
+    pub weight: u32,
+    pub fee: u64,
+    /// Absent on transactions returned by block endpoints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TxStatusFormat>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MerkleProofFormat {
     pub block_height: u32,
     pub merkle: Vec<String>,
     pub pos: u32,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct OutspentFormat {
     pub spent: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub txid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub vin: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub status: Option<TxStatusFormat>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AddressInfoFormat {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scripthash: Option<String>,
     pub chain_stats: ChainMempoolStats,
     pub mempool_stats: ChainMempoolStats,
-    pub scripthash: Option<String>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ChainMempoolStats {
-    pub funded_txo_count: i32,
-    pub funded_txo_sum: i64,
-    pub spent_txo_count: i32,
-    pub spent_txo_sum: i64,
-    pub tx_count: i32,
+    pub funded_txo_count: u64,
+    /// Not available on Elements-based chains.
+    #[cfg_attr(feature = "liquid", serde(default))]
+    pub funded_txo_sum: u64,
+    pub spent_txo_count: u64,
+    /// Not available on Elements-based chains.
+    #[cfg_attr(feature = "liquid", serde(default))]
+    pub spent_txo_sum: u64,
+    pub tx_count: u64,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct MemPoolFormat {
     pub count: u32,
-    pub vsize: u32,
-    pub total_fee: u32,
-    pub fee_histogram: Vec<Vec<f32>>,
+    pub vsize: u64,
+    pub total_fee: u64,
+    /// (feerate in sat/vB, vsize) tuples.
+    pub fee_histogram: Vec<(f64, u64)>,
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MempoolTxFormat {
     pub txid: String,
-    pub fee: u32,
+    pub fee: u64,
     pub vsize: u32,
     pub value: u64,
 }
@@ -0,0 +1,119 @@
+/// liquid reference Elements specific data structures returned by Liquid Esplora instances.
+/// Available with the `liquid` feature.
+/// Official API documentation is available at [Blockstream Esplora API](https://github.com/Blockstream/esplora/blob/master/API.md#assets)
+use serde::{Deserialize, Serialize};
+
+use crate::data::blockstream::TxStatusFormat;
+/// Signed block proof of a Liquid block.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockProofFormat {
+    pub challenge: String,
+    pub solution: String,
+}
+/// Peg-out data attached to an output sending funds back to the parent chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PegoutFormat {
+    pub genesis_hash: String,
+    pub scriptpubkey: String,
+    pub scriptpubkey_asm: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scriptpubkey_address: Option<String>,
+}
+/// Asset issuance or reissuance attached to an input.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssuanceFormat {
+    pub asset_id: String,
+    pub is_reissuance: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_blinding_nonce: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_hash: Option<String>,
+    pub asset_entropy: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assetamount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assetamountcommitment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenamount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenamountcommitment: Option<String>,
+}
+/// Input that issued an asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetIssuanceTxin {
+    pub txid: String,
+    pub vin: u32,
+}
+/// Outpoint spent by the issuance input.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetIssuancePrevout {
+    pub txid: String,
+    pub vout: u32,
+}
+/// Issuer entity of a registered asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetEntity {
+    pub domain: String,
+}
+/// Chain or mempool statistics of an asset.
+/// The native asset (L-BTC) reports peg statistics, issued assets report issuance statistics.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum AssetStats {
+    Native(NativeAssetStats),
+    Issued(IssuedAssetStats),
+}
+/// Chain or mempool statistics of the native asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NativeAssetStats {
+    pub tx_count: u64,
+    pub peg_in_count: u64,
+    pub peg_in_amount: u64,
+    pub peg_out_count: u64,
+    pub peg_out_amount: u64,
+    pub burn_count: u64,
+    pub burned_amount: u64,
+}
+/// Chain or mempool statistics of an issued asset. Amounts are None when issuances are blinded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssuedAssetStats {
+    pub tx_count: u64,
+    pub issuance_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_amount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burned_amount: Option<u64>,
+    pub has_blinded_issuances: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reissuance_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burned_reissuance_tokens: Option<u64>,
+}
+/// Asset information, with registry metadata when the asset is registered.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AssetFormat {
+    pub asset_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuance_txin: Option<AssetIssuanceTxin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuance_prevout: Option<AssetIssuancePrevout>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reissuance_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TxStatusFormat>,
+    pub chain_stats: AssetStats,
+    pub mempool_stats: AssetStats,
+    /// Registry contract, kept as raw JSON since its content is defined by the issuer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<AssetEntity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<String>,
+}
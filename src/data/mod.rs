@@ -1 +1,5 @@
 pub mod blockstream;
+#[cfg(feature = "liquid")]
+pub mod liquid;
+#[cfg(feature = "bitcoin")]
+pub(crate) mod bitcoin_types;
@@ -0,0 +1,374 @@
+//! Esplora endpoints shared by the async and blocking clients.
+//!
+//! Each function describes one route: its method, its path and how the response is decoded.
+//! Clients only build the endpoint, send its request through their transport and parse the response.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+
+use crate::data::blockstream::{
+    AddressInfoFormat, BlockFormat, BlockStatus, MemPoolFormat, MempoolTxFormat, MerkleProofFormat,
+    OutspentFormat, TransactionFormat, TxStatusFormat, UtxoFormat,
+};
+#[cfg(feature = "liquid")]
+use crate::data::liquid::AssetFormat;
+use crate::cache::Caching;
+use crate::error::{broadcast_error, decode_json, Error};
+use crate::transport::{Method, Request, Response};
+
+/// Description of a call to an Esplora route returning a `T`.
+pub(crate) struct Endpoint<T> {
+    method: Method,
+    path: String,
+    body: Option<String>,
+    parse: fn(&Request, Response) -> Result<T, Error>,
+    caching: Caching,
+    anchor: Anchor,
+}
+
+/// Block an immutable response is tied to, used to persist it and drop it if the block leaves the best chain.
+pub(crate) enum Anchor {
+    None,
+    /// The response belongs to this block.
+    Block(String),
+    /// The response is a transaction, tied to the block confirming it.
+    ConfirmingBlock,
+    /// The response is the status of this block.
+    BlockStatus(String),
+}
+
+impl<T> Endpoint<T> {
+    fn get(path: String, parse: fn(&Request, Response) -> Result<T, Error>) -> Self {
+        Endpoint {
+            method: Method::Get,
+            path,
+            body: None,
+            parse,
+            caching: Caching::Never,
+            anchor: Anchor::None,
+        }
+    }
+    fn cached(mut self, caching: Caching) -> Self {
+        self.caching = caching;
+        self
+    }
+    fn anchored(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+    /// Request to send to the Esplora instance at base_url.
+    pub(crate) fn request(&self, base_url: &str, timeout: Option<Duration>) -> Request {
+        Request {
+            method: self.method,
+            url: format!("{}{}", base_url, self.path),
+            body: self.body.clone(),
+            timeout,
+        }
+    }
+    /// Path of the route, relative to the base url.
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+    pub(crate) fn caching(&self) -> Caching {
+        self.caching
+    }
+    pub(crate) fn anchor(&self) -> &Anchor {
+        &self.anchor
+    }
+    /// Block the response is tied to: the block of the route, or the block confirming a transaction.
+    pub(crate) fn block(&self, response: &Response) -> Option<String> {
+        match &self.anchor {
+            Anchor::Block(hash) => Some(hash.clone()),
+            Anchor::ConfirmingBlock => self.confirming_block(response),
+            Anchor::None | Anchor::BlockStatus(_) => None,
+        }
+    }
+    /// Hash of the block confirming the transaction of a `/tx/:txid` response.
+    pub(crate) fn confirming_block(&self, response: &Response) -> Option<String> {
+        if let Anchor::ConfirmingBlock = self.anchor {
+            let tx: serde_json::Value = serde_json::from_slice(&response.body).ok()?;
+            if let (serde_json::Value::Bool(true), serde_json::Value::String(hash)) =
+                (&tx["status"]["confirmed"], &tx["status"]["block_hash"])
+            {
+                return Some(hash.clone());
+            }
+        }
+        None
+    }
+    /// Hash of the block and whether it is in the best chain, if the response is a block status.
+    pub(crate) fn block_status(&self, response: &Response) -> Option<(&str, bool)> {
+        match &self.anchor {
+            Anchor::BlockStatus(hash) if response.is_success() => Some((hash, in_best_chain(response))),
+            _ => None,
+        }
+    }
+    /// Decode the response returned for request.
+    pub(crate) fn parse(&self, request: &Request, response: Response) -> Result<T, Error> {
+        (self.parse)(request, response)
+    }
+}
+
+/// Whether a `/block/:hash/status` response says the block is in the best chain.
+pub(crate) fn in_best_chain(response: &Response) -> bool {
+    serde_json::from_slice::<serde_json::Value>(&response.body)
+        .map_or(true, |status| status["in_best_chain"] != false)
+}
+
+/// Validate an Esplora base url and remove its trailing slashes, so endpoint paths can be appended to it.
+pub(crate) fn base_url(url: &str) -> Result<String, Error> {
+    let url = url.trim();
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| Error::InvalidInput(format!("url {} must start with http:// or https://", url)))?;
+    if host.is_empty() || host.starts_with('/') {
+        return Err(Error::InvalidInput(format!("url {} has no host", url)));
+    }
+    if url.contains(|c: char| c == '?' || c == '#' || c.is_whitespace()) {
+        return Err(Error::InvalidInput(format!(
+            "url {} must not contain a query, a fragment or whitespaces",
+            url
+        )));
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Non-2xx responses are returned as Error::HttpStatus with Esplora message.
+fn check_status(response: Response) -> Result<Response, Error> {
+    if response.is_success() {
+        return Ok(response);
+    }
+    Err(Error::HttpStatus {
+        status: response.status,
+        body: String::from_utf8_lossy(&response.body).into_owned(),
+    })
+}
+
+fn json<T: DeserializeOwned>(request: &Request, response: Response) -> Result<T, Error> {
+    let body = text(request, response)?;
+    decode_json(&request.url, &body)
+}
+
+fn text(_request: &Request, response: Response) -> Result<String, Error> {
+    let response = check_status(response)?;
+    Ok(String::from_utf8_lossy(&response.body).into_owned())
+}
+
+fn bytes(_request: &Request, response: Response) -> Result<Vec<u8>, Error> {
+    Ok(check_status(response)?.body)
+}
+
+/// Whether a `/tx/:txid` response holds a confirmed transaction.
+fn confirmed_tx(response: &Response) -> bool {
+    serde_json::from_slice::<serde_json::Value>(&response.body)
+        .is_ok_and(|tx| tx["status"]["confirmed"] == true)
+}
+
+fn broadcast(_request: &Request, response: Response) -> Result<String, Error> {
+    let response = check_status(response).map_err(broadcast_error)?;
+    Ok(String::from_utf8_lossy(&response.body).into_owned())
+}
+
+pub(crate) fn block(hash: &str) -> Endpoint<BlockFormat> {
+    Endpoint::get(format!("/block/{}", hash), json)
+        .cached(Caching::Immutable)
+        .anchored(Anchor::Block(hash.to_string()))
+}
+pub(crate) fn block_status(hash: &str) -> Endpoint<BlockStatus> {
+    Endpoint::get(format!("/block/{}/status", hash), json)
+        .anchored(Anchor::BlockStatus(hash.to_string()))
+}
+pub(crate) fn block_txs(hash: &str, start_index: Option<i32>) -> Endpoint<Vec<TransactionFormat>> {
+    let path = if let Some(i) = start_index {
+        format!("/block/{}/txs/{}", hash, i)
+    } else {
+        format!("/block/{}/txs", hash)
+    };
+    Endpoint::get(path, json)
+        .cached(Caching::Immutable)
+        .anchored(Anchor::Block(hash.to_string()))
+}
+pub(crate) fn block_txids(hash: &str) -> Endpoint<Vec<String>> {
+    Endpoint::get(format!("/block/{}/txids", hash), json)
+        .cached(Caching::Immutable)
+        .anchored(Anchor::Block(hash.to_string()))
+}
+pub(crate) fn block_txid_at_index(hash: &str, index: i32) -> Endpoint<String> {
+    Endpoint::get(format!("/block/{}/txid/{}", hash, index), text)
+        .cached(Caching::Immutable)
+        .anchored(Anchor::Block(hash.to_string()))
+}
+pub(crate) fn block_raw(hash: &str) -> Endpoint<Vec<u8>> {
+    Endpoint::get(format!("/block/{}/raw", hash), bytes)
+        .cached(Caching::Immutable)
+        .anchored(Anchor::Block(hash.to_string()))
+}
+pub(crate) fn block_height(height: i32) -> Endpoint<String> {
+    Endpoint::get(format!("/block-height/{}", height), text)
+}
+pub(crate) fn blocks(start_height: i32) -> Endpoint<Vec<BlockFormat>> {
+    Endpoint::get(format!("/blocks/{}", start_height), json)
+}
+pub(crate) fn blocks_tip_height() -> Endpoint<i32> {
+    Endpoint::get("/blocks/tip/height".to_string(), json).cached(Caching::Volatile)
+}
+pub(crate) fn blocks_tip_hash() -> Endpoint<String> {
+    Endpoint::get("/blocks/tip/hash".to_string(), text).cached(Caching::Volatile)
+}
+pub(crate) fn tx(txid: &str) -> Endpoint<TransactionFormat> {
+    Endpoint::get(format!("/tx/{}", txid), json)
+        .cached(Caching::ImmutableIf(confirmed_tx))
+        .anchored(Anchor::ConfirmingBlock)
+}
+pub(crate) fn tx_status(txid: &str) -> Endpoint<TxStatusFormat> {
+    Endpoint::get(format!("/tx/{}/status", txid), json)
+}
+pub(crate) fn tx_raw(txid: &str) -> Endpoint<Vec<u8>> {
+    Endpoint::get(format!("/tx/{}/raw", txid), bytes)
+}
+pub(crate) fn tx_hex(txid: &str) -> Endpoint<String> {
+    Endpoint::get(format!("/tx/{}/hex", txid), text)
+}
+pub(crate) fn tx_merkleblock_proof(txid: &str) -> Endpoint<String> {
+    Endpoint::get(format!("/tx/{}/merkleblock-proof", txid), text)
+}
+pub(crate) fn tx_merkle_proof(txid: &str) -> Endpoint<MerkleProofFormat> {
+    Endpoint::get(format!("/tx/{}/merkle-proof", txid), json)
+}
+pub(crate) fn tx_outspend(txid: &str, vout: Option<i32>) -> Endpoint<OutspentFormat> {
+    let path = if let Some(vout_idx) = vout {
+        format!("/tx/{}/outspend/{}", txid, vout_idx)
+    } else {
+        format!("/tx/{}/outspend", txid)
+    };
+    Endpoint::get(path, json)
+}
+pub(crate) fn tx_outspends(txid: &str) -> Endpoint<Vec<OutspentFormat>> {
+    Endpoint::get(format!("/tx/{}/outspends", txid), json)
+}
+pub(crate) fn post_tx(hex_transaction: &str) -> Endpoint<String> {
+    Endpoint {
+        method: Method::Post,
+        path: "/tx".to_string(),
+        body: Some(hex_transaction.to_string()),
+        parse: broadcast,
+        caching: Caching::Never,
+        anchor: Anchor::None,
+    }
+}
+pub(crate) fn address(address: &str) -> Endpoint<AddressInfoFormat> {
+    Endpoint::get(format!("/address/{}", address), json)
+}
+pub(crate) fn scripthash(scripthash: &str) -> Endpoint<AddressInfoFormat> {
+    Endpoint::get(format!("/scripthash/{}", scripthash), json)
+}
+pub(crate) fn address_txs(address: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/address/{}/txs", address), json)
+}
+pub(crate) fn scripthash_txs(scripthash: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/scripthash/{}/txs", scripthash), json)
+}
+pub(crate) fn address_txs_chain(address: &str, txid: Option<&str>) -> Endpoint<Vec<TransactionFormat>> {
+    let path = if let Some(id) = txid {
+        format!("/address/{}/txs/chain/{}", address, id)
+    } else {
+        format!("/address/{}/txs/chain", address)
+    };
+    Endpoint::get(path, json)
+}
+pub(crate) fn scripthash_txs_chain(
+    scripthash: &str,
+    txid: Option<&str>,
+) -> Endpoint<Vec<TransactionFormat>> {
+    let path = if let Some(id) = txid {
+        format!("/scripthash/{}/txs/chain/{}", scripthash, id)
+    } else {
+        format!("/scripthash/{}/txs/chain", scripthash)
+    };
+    Endpoint::get(path, json)
+}
+pub(crate) fn address_txs_mempool(address: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/address/{}/txs/mempool", address), json)
+}
+pub(crate) fn scripthash_txs_mempool(scripthash: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/scripthash/{}/txs/mempool", scripthash), json)
+}
+pub(crate) fn address_utxo(address: &str) -> Endpoint<Vec<UtxoFormat>> {
+    Endpoint::get(format!("/address/{}/utxo", address), json)
+}
+pub(crate) fn scripthash_utxo(scripthash: &str) -> Endpoint<Vec<UtxoFormat>> {
+    Endpoint::get(format!("/scripthash/{}/utxo", scripthash), json)
+}
+pub(crate) fn address_prefix(prefix: &str) -> Endpoint<Vec<String>> {
+    Endpoint::get(format!("/address-prefix/{}", prefix), json)
+}
+pub(crate) fn mempool() -> Endpoint<MemPoolFormat> {
+    Endpoint::get("/mempool".to_string(), json).cached(Caching::Volatile)
+}
+pub(crate) fn mempool_txids() -> Endpoint<Vec<String>> {
+    Endpoint::get("/mempool/txids".to_string(), json).cached(Caching::Volatile)
+}
+pub(crate) fn mempool_recent() -> Endpoint<Vec<MempoolTxFormat>> {
+    Endpoint::get("/mempool/recent".to_string(), json).cached(Caching::Volatile)
+}
+pub(crate) fn fee_estimates() -> Endpoint<HashMap<String, f32>> {
+    Endpoint::get("/fee-estimates".to_string(), json).cached(Caching::Volatile)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset(asset_id: &str) -> Endpoint<AssetFormat> {
+    Endpoint::get(format!("/asset/{}", asset_id), json)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset_txs(asset_id: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/asset/{}/txs", asset_id), json)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset_txs_mempool(asset_id: &str) -> Endpoint<Vec<TransactionFormat>> {
+    Endpoint::get(format!("/asset/{}/txs/mempool", asset_id), json)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset_txs_chain(asset_id: &str, txid: Option<&str>) -> Endpoint<Vec<TransactionFormat>> {
+    let path = if let Some(id) = txid {
+        format!("/asset/{}/txs/chain/{}", asset_id, id)
+    } else {
+        format!("/asset/{}/txs/chain", asset_id)
+    };
+    Endpoint::get(path, json)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset_supply(asset_id: &str) -> Endpoint<u64> {
+    Endpoint::get(format!("/asset/{}/supply", asset_id), json)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn asset_supply_decimal(asset_id: &str) -> Endpoint<String> {
+    Endpoint::get(format!("/asset/{}/supply/decimal", asset_id), text)
+}
+#[cfg(feature = "liquid")]
+pub(crate) fn assets_registry(
+    start_index: Option<u32>,
+    limit: Option<u32>,
+    sort_field: Option<&str>,
+    sort_dir: Option<&str>,
+) -> Endpoint<Vec<AssetFormat>> {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if let Some(start_index) = start_index {
+        query.append_pair("start_index", &start_index.to_string());
+    }
+    if let Some(limit) = limit {
+        query.append_pair("limit", &limit.to_string());
+    }
+    if let Some(sort_field) = sort_field {
+        query.append_pair("sort_field", sort_field);
+    }
+    if let Some(sort_dir) = sort_dir {
+        query.append_pair("sort_dir", sort_dir);
+    }
+    let query = query.finish();
+    let path = if query.is_empty() {
+        "/assets/registry".to_string()
+    } else {
+        format!("/assets/registry?{}", query)
+    };
+    Endpoint::get(path, json)
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::quorum::QuorumVote;
+
+/// Maximum number of characters of a response body kept in a decode error.
+const BODY_SNIPPET_LEN: usize = 256;
+
+/// Error returned by both the async and blocking clients.
+///
+/// Variants allow callers to tell apart network failures, Esplora error responses,
+/// unexpected payloads and invalid arguments, for example to decide whether a call should be retried.
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or the response could not be read, with the underlying transport error.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// Esplora answered with a non-2xx status code. `body` holds the plain-text error message returned by Esplora.
+    HttpStatus { status: u16, body: String },
+    /// The transaction broadcast by `post_tx` was rejected by bitcoind, with its reject code and message.
+    BroadcastRejected { code: i32, message: String },
+    /// The response body could not be deserialized. `body` holds the beginning of the offending response.
+    Decode {
+        url: String,
+        body: String,
+        source: serde_json::Error,
+    },
+    /// A value returned by Esplora could not be converted into the requested type.
+    Parse(String),
+    /// An argument or an option given to the client is invalid.
+    InvalidInput(String),
+    /// Fewer than `required` servers of a quorum read returned the same value, or several different values each
+    /// reached `required` votes. `votes` holds the answer of each server.
+    QuorumNotReached { required: usize, votes: Vec<QuorumVote> },
+    /// Answers of Esplora contradict each other, e.g. a block returning fewer transactions than its `tx_count`.
+    Inconsistent(String),
+}
+
+impl Error {
+    /// HTTP status code returned by Esplora, if any.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::HttpStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "transport error: {}", err),
+            Error::HttpStatus { status, body } => write!(f, "http status {}: {}", status, body),
+            Error::BroadcastRejected { code, message } => {
+                write!(f, "transaction rejected ({}): {}", code, message)
+            }
+            Error::Decode { url, source, .. } => {
+                write!(f, "unable to decode response from {}: {}", url, source)
+            }
+            Error::Parse(message) => write!(f, "unable to parse value: {}", message),
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::QuorumNotReached { required, votes } => {
+                write!(f, "quorum of {} not reached:", required)?;
+                for vote in votes {
+                    match &vote.result {
+                        Ok(value) => write!(f, " [{}: {}]", vote.url, value)?,
+                        Err(err) => write!(f, " [{}: {}]", vote.url, err)?,
+                    }
+                }
+                Ok(())
+            }
+            Error::Inconsistent(message) => write!(f, "inconsistent response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(err) => Some(err.as_ref()),
+            Error::Decode { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Transport(Box::new(err))
+    }
+}
+
+/// Reject details embedded by Esplora in a failed broadcast body.
+#[derive(Deserialize)]
+struct RpcReject {
+    code: i32,
+    message: String,
+}
+
+/// Turn an Esplora broadcast failure (`sendrawtransaction RPC error: {"code":-26,"message":"..."}`)
+/// into `Error::BroadcastRejected`. Other errors are returned unchanged.
+pub(crate) fn broadcast_error(err: Error) -> Error {
+    if let Error::HttpStatus { body, .. } = &err {
+        let reject = body
+            .find('{')
+            .and_then(|start| serde_json::from_str::<RpcReject>(body[start..].trim()).ok());
+        if let Some(RpcReject { code, message }) = reject {
+            return Error::BroadcastRejected { code, message };
+        }
+    }
+    err
+}
+
+/// Deserialize a JSON response body, keeping url and a body snippet on failure.
+pub(crate) fn decode_json<T: DeserializeOwned>(url: &str, body: &str) -> Result<T, Error> {
+    serde_json::from_str(body).map_err(|source| Error::Decode {
+        url: url.to_string(),
+        body: body.chars().take(BODY_SNIPPET_LEN).collect(),
+        source,
+    })
+}
@@ -0,0 +1,173 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::transport::Response;
+
+/// Fallback Esplora endpoints tried in order when the client url is unavailable.
+///
+/// An endpoint failing with a transport error or a 5xx status is marked dead and the call is sent to the next one.
+/// Dead endpoints are skipped until `probe_interval` elapsed, then probed with `GET /blocks/tip/height`
+/// before being used again. When every endpoint is dead, they are all tried anyway.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::Failover;
+///
+/// let failover = Failover {
+///     fallback_urls: vec!["https://blockstream.info/api".to_string()],
+///     ..Default::default()
+/// };
+/// ````
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failover {
+    /// Endpoints used after the client url, in order.
+    pub fallback_urls: Vec<String>,
+    /// Time a dead endpoint is skipped before being probed again.
+    pub probe_interval: Duration,
+}
+
+impl Default for Failover {
+    /// No fallback endpoint, dead endpoints probed again after 30s.
+    fn default() -> Self {
+        Failover {
+            fallback_urls: Vec::new(),
+            probe_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Health of an endpoint of a failover client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EndpointHealth {
+    pub url: String,
+    /// False once the endpoint failed, until it answers again.
+    pub healthy: bool,
+    /// Number of failures since the endpoint last answered.
+    pub consecutive_failures: u32,
+    /// Number of responses served by the endpoint.
+    pub served: u64,
+}
+
+/// Result of a call with the url of the endpoint that served it, see
+/// [ApiClient::served](crate::async_impl::ApiClient::served).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Served<T> {
+    /// Url of the endpoint that sent the response, None when it was answered from a cache.
+    pub url: Option<String>,
+    pub value: T,
+}
+
+/// Endpoints and health shared by clones of a failover client.
+#[derive(Debug)]
+pub(crate) struct Endpoints {
+    urls: Vec<String>,
+    probe_interval: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    health: Vec<Health>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Health {
+    consecutive_failures: u32,
+    dead_since: Option<Instant>,
+    served: u64,
+}
+
+/// Endpoint selected for a call.
+pub(crate) struct Candidate<'a> {
+    pub(crate) index: usize,
+    pub(crate) url: &'a str,
+    /// The endpoint is dead and must answer a probe before being used.
+    pub(crate) probe: bool,
+}
+
+impl Endpoints {
+    pub(crate) fn new(url: &str, failover: Failover) -> Self {
+        let mut urls = vec![url.to_string()];
+        urls.extend(failover.fallback_urls);
+        Endpoints {
+            state: Mutex::new(State {
+                health: vec![Health::default(); urls.len()],
+            }),
+            urls,
+            probe_interval: failover.probe_interval,
+        }
+    }
+    /// Endpoints to try, in order: live endpoints and dead ones due for a probe, then the other dead ones as last resort.
+    pub(crate) fn candidates(&self) -> Vec<Candidate<'_>> {
+        let state = self.lock();
+        let now = Instant::now();
+        let mut candidates = Vec::with_capacity(self.urls.len());
+        let mut last_resort = Vec::new();
+        for (index, url) in self.urls.iter().enumerate() {
+            match state.health[index].dead_since {
+                None => candidates.push(Candidate { index, url, probe: false }),
+                Some(since) if now.duration_since(since) >= self.probe_interval => {
+                    candidates.push(Candidate { index, url, probe: true })
+                }
+                Some(_) => last_resort.push(Candidate { index, url, probe: false }),
+            }
+        }
+        candidates.extend(last_resort);
+        candidates
+    }
+    pub(crate) fn mark_success(&self, index: usize) {
+        let mut state = self.lock();
+        let health = &mut state.health[index];
+        health.consecutive_failures = 0;
+        health.dead_since = None;
+        health.served += 1;
+    }
+    pub(crate) fn mark_alive(&self, index: usize) {
+        let mut state = self.lock();
+        state.health[index].consecutive_failures = 0;
+        state.health[index].dead_since = None;
+    }
+    pub(crate) fn mark_failure(&self, index: usize) {
+        let mut state = self.lock();
+        let health = &mut state.health[index];
+        health.consecutive_failures += 1;
+        health.dead_since = Some(Instant::now());
+    }
+    pub(crate) fn health(&self) -> Vec<EndpointHealth> {
+        let state = self.lock();
+        self.urls
+            .iter()
+            .zip(&state.health)
+            .map(|(url, health)| EndpointHealth {
+                url: url.clone(),
+                healthy: health.dead_since.is_none(),
+                consecutive_failures: health.consecutive_failures,
+                served: health.served,
+            })
+            .collect()
+    }
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Whether outcome means the endpoint is unavailable and the next one should be tried.
+pub(crate) fn is_unavailable(outcome: &Result<Response, Error>) -> bool {
+    match outcome {
+        Ok(response) => response.status >= 500,
+        Err(Error::Transport(_)) => true,
+        Err(_) => false,
+    }
+}
+
+/// Error returned when every endpoint was unavailable, from the outcome of the last one.
+pub(crate) fn unavailable_error(outcome: Result<Response, Error>) -> Error {
+    match outcome {
+        Ok(response) => Error::HttpStatus {
+            status: response.status,
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+        },
+        Err(err) => err,
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+use crate::data::blockstream::TransactionFormat;
+use crate::endpoint::{self, Endpoint};
+
+/// Address or scripthash whose history is walked.
+#[derive(Debug, Clone)]
+pub(crate) enum Target {
+    Address(String),
+    ScriptHash(String),
+}
+
+#[derive(Debug, Clone)]
+enum Phase {
+    Mempool,
+    /// Confirmed pages, after the last txid seen.
+    Chain(Option<String>),
+    Done,
+}
+
+/// Pagination state of a transaction history, shared by the async stream and the blocking iterator.
+///
+/// Mempool transactions come first, then confirmed transactions newest first, page after page
+/// using the last txid of each page, until an empty page or a stop condition.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    target: Target,
+    phase: Phase,
+    buffer: VecDeque<TransactionFormat>,
+    stop_txid: Option<String>,
+    min_height: Option<u32>,
+}
+
+impl History {
+    pub(crate) fn new(target: Target) -> Self {
+        History {
+            target,
+            phase: Phase::Mempool,
+            buffer: VecDeque::new(),
+            stop_txid: None,
+            min_height: None,
+        }
+    }
+    pub(crate) fn skip_mempool(&mut self) {
+        if let Phase::Mempool = self.phase {
+            self.phase = Phase::Chain(None);
+        }
+    }
+    pub(crate) fn stop_at_txid(&mut self, txid: String) {
+        self.stop_txid = Some(txid);
+    }
+    pub(crate) fn min_height(&mut self, height: u32) {
+        self.min_height = Some(height);
+    }
+    /// Next transaction already fetched, None when a page must be fetched or the history is over.
+    pub(crate) fn pop(&mut self) -> Option<TransactionFormat> {
+        let tx = self.buffer.pop_front()?;
+        let below_min_height = match (self.min_height, tx.status.as_ref().and_then(|status| status.block_height)) {
+            (Some(min_height), Some(height)) => height < min_height,
+            _ => false,
+        };
+        if below_min_height || self.stop_txid.as_deref() == Some(tx.txid.as_str()) {
+            self.finish();
+            return None;
+        }
+        Some(tx)
+    }
+    /// Endpoint of the next page, None when the history is over.
+    pub(crate) fn next_page(&self) -> Option<Endpoint<Vec<TransactionFormat>>> {
+        match (&self.phase, &self.target) {
+            (Phase::Mempool, Target::Address(address)) => Some(endpoint::address_txs_mempool(address)),
+            (Phase::Mempool, Target::ScriptHash(scripthash)) => Some(endpoint::scripthash_txs_mempool(scripthash)),
+            (Phase::Chain(last_seen), Target::Address(address)) => {
+                Some(endpoint::address_txs_chain(address, last_seen.as_deref()))
+            }
+            (Phase::Chain(last_seen), Target::ScriptHash(scripthash)) => {
+                Some(endpoint::scripthash_txs_chain(scripthash, last_seen.as_deref()))
+            }
+            (Phase::Done, _) => None,
+        }
+    }
+    /// Store the page fetched from the endpoint returned by next_page.
+    pub(crate) fn push_page(&mut self, page: Vec<TransactionFormat>) {
+        self.phase = match self.phase {
+            Phase::Mempool => Phase::Chain(None),
+            Phase::Chain(_) => match page.last() {
+                Some(last) => Phase::Chain(Some(last.txid.clone())),
+                None => Phase::Done,
+            },
+            Phase::Done => Phase::Done,
+        };
+        self.buffer.extend(page);
+    }
+    /// End the history, e.g. after an error.
+    pub(crate) fn finish(&mut self) {
+        self.phase = Phase::Done;
+        self.buffer.clear();
+    }
+}
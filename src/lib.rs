@@ -2,31 +2,37 @@
 //! 
 //! This library provide a simple wrapper to use Blockstream API or self hosted [Esplora - Electrs API](https://github.com/Blockstream/electrs) based on reqwest framework.
 //! Wrapper can be used with custom configuration according to your needs.
-//! Liquid features not implemented for the moment.
+//! Liquid / Elements fields and asset endpoints are available with the `liquid` feature.
 //! 
 //! ## Optionnal Features 
 //! - **blocking**: Provides the [blocking](blocking) client API.
+//! - **liquid**: Adds Elements-specific fields to [data](data) structures and the asset endpoints used by Liquid Esplora instances.
+//! - **bitcoin**: Adds [rust-bitcoin](https://docs.rs/bitcoin) typed accessors on data structures, raw transaction and block decoding, and broadcast of `bitcoin::Transaction`.
+//! - **testing**: Provides the [testing](testing) mock Esplora server and the record/replay transports to test clients offline.
+//! - **cli**: Builds the `esplora` command-line binary, exposing the [blocking](blocking) client lookups as subcommands.
 //! 
 //! ## Usage
 //! 
-//! Simple async usage : 
+//! Simple blocking usage : 
 //! 
 //! ````rust
-//! fn main(){
-//!    let client = esplora_api::blocking::ApiClient::new("https://blockstream.info/testnet/api/", None).unwrap();
-//!    let res = client.get_address("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk").unwrap();
+//! # #[cfg(feature = "blocking")]
+//! fn run() -> Result<(), esplora_api::Error> {
+//!    let client = esplora_api::blocking::ApiClient::blockstream(esplora_api::Network::Testnet)?;
+//!    let res = client.get_address("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk")?;
 //!    println!("{:?}",res);
+//!    Ok(())
 //! }
 //! ````
 //! 
 //! Custom reqwest client:
 //! 
-//! ````rust
+//! ````rust,no_run
 //! use reqwest;
 //! use reqwest::header;
 //! 
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> Result<(), esplora_api::Error> {
 //!    let mut headers = header::HeaderMap::new();
 //!    headers.insert(header::AUTHORIZATION,header::HeaderValue::from_static("secret"));
 //!    let reqwest_client = reqwest::Client::builder().default_headers(headers).build()?;
@@ -37,10 +43,40 @@
 //! }
 //!````
 //! 
+//! Other HTTP clients can be plugged in by implementing [async_impl::EsploraTransport](async_impl::EsploraTransport)
+//! or `blocking::EsploraTransport`, see [ApiClient::with_transport](async_impl::ApiClient::with_transport).
 //! 
-//! 
+#[macro_use]
+mod options;
 pub mod async_impl;
+mod blocks;
+mod cache;
 pub mod data;
+mod endpoint;
+mod error;
+mod failover;
+mod history;
+mod network;
+mod quorum;
+mod rate_limit;
+mod retry;
+mod store;
+pub mod transport;
+pub use self::cache::{CachePolicy, CacheStats};
+pub use self::error::Error;
+pub use self::failover::{EndpointHealth, Failover, Served};
+pub use self::network::Network;
+pub use self::options::{ClientOptions, HeadersOptions, TlsOptions};
+pub use self::quorum::QuorumVote;
+pub use self::rate_limit::RateLimit;
+pub use self::retry::RetryPolicy;
+pub use self::store::{CacheStore, FsStore};
+#[cfg(feature = "sled")]
+pub use self::store::SledStore;
 
 #[cfg(feature = "blocking")]
 pub mod blocking;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "bitcoin")]
+pub use bitcoin;
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Chain served by an Esplora instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    /// Bitcoin mainnet.
+    Bitcoin,
+    Testnet,
+    Testnet4,
+    Signet,
+    Regtest,
+    /// Liquid mainnet.
+    Liquid,
+    LiquidTestnet,
+}
+
+impl Network {
+    /// All networks.
+    pub const ALL: [Network; 7] = [
+        Network::Bitcoin,
+        Network::Testnet,
+        Network::Testnet4,
+        Network::Signet,
+        Network::Regtest,
+        Network::Liquid,
+        Network::LiquidTestnet,
+    ];
+    /// Public Esplora API of blockstream.info for this network, if any.
+    pub fn blockstream_url(self) -> Option<&'static str> {
+        match self {
+            Network::Bitcoin => Some("https://blockstream.info/api"),
+            Network::Testnet => Some("https://blockstream.info/testnet/api"),
+            Network::Signet => Some("https://blockstream.info/signet/api"),
+            Network::Liquid => Some("https://blockstream.info/liquid/api"),
+            Network::LiquidTestnet => Some("https://blockstream.info/liquidtestnet/api"),
+            Network::Testnet4 | Network::Regtest => None,
+        }
+    }
+    /// Public Esplora API of mempool.space (liquid.network for Liquid) for this network, if any.
+    pub fn mempool_space_url(self) -> Option<&'static str> {
+        match self {
+            Network::Bitcoin => Some("https://mempool.space/api"),
+            Network::Testnet => Some("https://mempool.space/testnet/api"),
+            Network::Testnet4 => Some("https://mempool.space/testnet4/api"),
+            Network::Signet => Some("https://mempool.space/signet/api"),
+            Network::Liquid => Some("https://liquid.network/api"),
+            Network::LiquidTestnet => Some("https://liquid.network/liquidtestnet/api"),
+            Network::Regtest => None,
+        }
+    }
+    /// Whether this is a Liquid / Elements network.
+    pub fn is_liquid(self) -> bool {
+        matches!(self, Network::Liquid | Network::LiquidTestnet)
+    }
+    /// Matching rust-bitcoin network, None for Liquid networks.
+    #[cfg(feature = "bitcoin")]
+    pub fn bitcoin_network(self) -> Option<bitcoin::Network> {
+        match self {
+            Network::Bitcoin => Some(bitcoin::Network::Bitcoin),
+            Network::Testnet => Some(bitcoin::Network::Testnet),
+            Network::Testnet4 => Some(bitcoin::Network::Testnet4),
+            Network::Signet => Some(bitcoin::Network::Signet),
+            Network::Regtest => Some(bitcoin::Network::Regtest),
+            Network::Liquid | Network::LiquidTestnet => None,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Network::Bitcoin => "bitcoin",
+            Network::Testnet => "testnet",
+            Network::Testnet4 => "testnet4",
+            Network::Signet => "signet",
+            Network::Regtest => "regtest",
+            Network::Liquid => "liquid",
+            Network::LiquidTestnet => "liquidtestnet",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Network {
+    type Err = Error;
+    /// Parse a network name as displayed, `mainnet` is accepted for bitcoin.
+    fn from_str(name: &str) -> Result<Self, Error> {
+        match name.to_ascii_lowercase().as_str() {
+            "bitcoin" | "mainnet" => Ok(Network::Bitcoin),
+            "testnet" => Ok(Network::Testnet),
+            "testnet4" => Ok(Network::Testnet4),
+            "signet" => Ok(Network::Signet),
+            "regtest" => Ok(Network::Regtest),
+            "liquid" => Ok(Network::Liquid),
+            "liquidtestnet" => Ok(Network::LiquidTestnet),
+            _ => Err(Error::InvalidInput(format!("unknown network {}", name))),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cache::CachePolicy;
+use crate::error::Error;
+use crate::failover::Failover;
+use crate::rate_limit::RateLimit;
+use crate::retry::RetryPolicy;
+use crate::store::CacheStore;
+
+/// Number of requests sent at once by the batch methods when not set in the options.
+pub(crate) const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Client basics options used to custom reqwest client, shared by the async and blocking clients.
+///
+/// Fields can be set directly or with the chainable setters.
+///
+/// Example :
+/// ````rust
+/// use std::time::Duration;
+/// use esplora_api::ClientOptions;
+///
+/// let options = ClientOptions::new()
+///     .authorization("secret")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-wallet/1.0")
+///     .header("x-api-key", "key");
+/// ````
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub headers: Option<HeadersOptions>,
+    /// Headers sent with every request, as (name, value) pairs.
+    pub default_headers: Vec<(String, String)>,
+    /// User agent sent with every request.
+    pub user_agent: Option<String>,
+    /// Timeout of the connection to Esplora.
+    pub connect_timeout: Option<Duration>,
+    /// Total timeout of a request, from connection to the end of the response body.
+    /// Can be overridden per call with `ApiClient::with_timeout`.
+    pub timeout: Option<Duration>,
+    /// Proxy url used for every request, e.g. `socks5://127.0.0.1:9050` or `http://proxy:3128`.
+    pub proxy: Option<String>,
+    pub tls: TlsOptions,
+    /// Retry transient failures, see [RetryPolicy](crate::RetryPolicy).
+    pub retry: Option<RetryPolicy>,
+    /// Limit the rate of requests, see [RateLimit](crate::RateLimit).
+    pub rate_limit: Option<RateLimit>,
+    /// Fall back to other endpoints when the url is unavailable, see [Failover](crate::Failover).
+    pub failover: Option<Failover>,
+    /// Number of requests sent at once by the batch methods such as `get_txs`, 8 by default.
+    pub batch_concurrency: Option<usize>,
+    /// Cache responses in memory, see [CachePolicy](crate::CachePolicy).
+    pub cache: Option<CachePolicy>,
+    /// Keep the responses tied to a block in a store, also across restarts, see [CacheStore](crate::CacheStore).
+    pub persistent_cache: Option<Arc<dyn CacheStore>>,
+    /// Send identical GET requests made at the same time only once. Async client only.
+    pub single_flight: bool,
+}
+/// Headers options can be used to use authorization header
+#[derive(Debug, Clone, Default)]
+pub struct HeadersOptions {
+    pub authorization: Option<String>,
+}
+/// TLS options, e.g. to reach a self-hosted Esplora using a private certificate authority.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// PEM encoded certificates trusted in addition to the system ones.
+    pub root_certificates: Vec<Vec<u8>>,
+    /// Accept any server certificate. Only use it against a trusted server, e.g. in tests.
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// Every option setter, expanded by `$setters` (`option_setters` or `builder_setters`) so each option
+/// added here can be set on ClientOptions and on the async and blocking `ApiClientBuilder`.
+macro_rules! client_option_list {
+    ($setters:ident) => {
+        $setters! {
+            /// Authorization header sent with every request.
+            authorization(options, authorization: impl Into<String>) {
+                options.headers = Some(HeadersOptions {
+                    authorization: Some(authorization.into()),
+                });
+            }
+            /// Header sent with every request.
+            header(options, name: impl Into<String>, value: impl Into<String>) {
+                options.default_headers.push((name.into(), value.into()));
+            }
+            /// User agent sent with every request.
+            user_agent(options, user_agent: impl Into<String>) {
+                options.user_agent = Some(user_agent.into());
+            }
+            /// Timeout of the connection to Esplora.
+            connect_timeout(options, timeout: std::time::Duration) {
+                options.connect_timeout = Some(timeout);
+            }
+            /// Total timeout of a request.
+            timeout(options, timeout: std::time::Duration) {
+                options.timeout = Some(timeout);
+            }
+            /// Proxy url used for every request.
+            proxy(options, proxy: impl Into<String>) {
+                options.proxy = Some(proxy.into());
+            }
+            /// Trust a PEM encoded certificate in addition to the system ones.
+            root_certificate(options, pem: impl Into<Vec<u8>>) {
+                options.tls.root_certificates.push(pem.into());
+            }
+            /// Accept any server certificate.
+            danger_accept_invalid_certs(options, accept: bool) {
+                options.tls.danger_accept_invalid_certs = accept;
+            }
+            /// Retry transient failures.
+            retry(options, retry: crate::RetryPolicy) {
+                options.retry = Some(retry);
+            }
+            /// Limit the rate of requests.
+            rate_limit(options, rate_limit: crate::RateLimit) {
+                options.rate_limit = Some(rate_limit);
+            }
+            /// Fall back to other endpoints when the url is unavailable.
+            failover(options, failover: crate::Failover) {
+                options.failover = Some(failover);
+            }
+            /// Number of requests sent at once by the batch methods.
+            batch_concurrency(options, concurrency: usize) {
+                options.batch_concurrency = Some(concurrency);
+            }
+            /// Cache responses in memory.
+            cache(options, cache: crate::CachePolicy) {
+                options.cache = Some(cache);
+            }
+            /// Keep the responses tied to a block in store.
+            persistent_cache(options, store: impl crate::CacheStore + 'static) {
+                options.persistent_cache = Some(Arc::new(store));
+            }
+            /// Send identical GET requests made at the same time only once. Async client only.
+            single_flight(options, enabled: bool) {
+                options.single_flight = enabled;
+            }
+        }
+    };
+}
+
+/// Chainable setters of ClientOptions, from `client_option_list`.
+macro_rules! option_setters {
+    ($($(#[$doc:meta])* $name:ident($options:ident $(, $arg:ident: $ty:ty)*) $body:block)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name(mut self $(, $arg: $ty)*) -> Self {
+                let $options = &mut self;
+                $body
+                self
+            }
+        )*
+    };
+}
+
+/// Setters of the async and blocking `ApiClientBuilder` forwarded to their `options` field, from `client_option_list`.
+macro_rules! builder_setters {
+    ($($(#[$doc:meta])* $name:ident($options:ident $(, $arg:ident: $ty:ty)*) $body:block)*) => {
+        /// Replace all the options set so far.
+        pub fn options(mut self, options: crate::ClientOptions) -> Self {
+            self.options = options;
+            self
+        }
+        $(
+            #[doc = concat!("See [ClientOptions::", stringify!($name), "](crate::ClientOptions::", stringify!($name), ").")]
+            pub fn $name(mut self $(, $arg: $ty)*) -> Self {
+                self.options = self.options.$name($($arg),*);
+                self
+            }
+        )*
+    };
+}
+
+impl ClientOptions {
+    /// New options with defaults: no timeout, no retry, no rate limit.
+    pub fn new() -> Self {
+        ClientOptions::default()
+    }
+    client_option_list!(option_setters);
+}
+
+impl ClientOptions {
+    /// Fail if options applied by the reqwest client are set, as a custom transport would ignore them.
+    pub(crate) fn check_custom_transport(&self) -> Result<(), Error> {
+        let ignored: Vec<&str> = [
+            ("authorization", self.headers.as_ref().is_some_and(|headers| headers.authorization.is_some())),
+            ("header", !self.default_headers.is_empty()),
+            ("user_agent", self.user_agent.is_some()),
+            ("connect_timeout", self.connect_timeout.is_some()),
+            ("proxy", self.proxy.is_some()),
+            ("root_certificate", !self.tls.root_certificates.is_empty()),
+            ("danger_accept_invalid_certs", self.tls.danger_accept_invalid_certs),
+        ]
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect();
+        if ignored.is_empty() {
+            return Ok(());
+        }
+        Err(Error::InvalidInput(format!(
+            "{} cannot be applied to a custom transport, configure the transport instead",
+            ignored.join(", ")
+        )))
+    }
+}
+
+/// Apply HTTP options to an async or blocking reqwest ClientBuilder, which share method names.
+#[cfg(feature = "reqwest")]
+macro_rules! configure_reqwest {
+    ($options:expr, $builder:expr) => {{
+        let options = $options;
+        let mut builder = $builder.default_headers(options.header_map()?);
+        if let Some(user_agent) = &options.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        if let Some(timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &options.proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str())
+                .map_err(|err| Error::InvalidInput(format!("invalid proxy url {}: {}", proxy, err)))?;
+            builder = builder.proxy(proxy);
+        }
+        for pem in &options.tls.root_certificates {
+            let certificate = reqwest::Certificate::from_pem(pem)
+                .map_err(|err| Error::InvalidInput(format!("invalid root certificate: {}", err)))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        builder = builder.danger_accept_invalid_certs(options.tls.danger_accept_invalid_certs);
+        Ok(builder.build()?)
+    }};
+}
+
+#[cfg(feature = "reqwest")]
+impl ClientOptions {
+    /// Headers sent with every request.
+    fn header_map(&self) -> Result<reqwest::header::HeaderMap, Error> {
+        use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+
+        let mut headers_map = HeaderMap::new();
+        // header::AUTHORIZATION
+        if let Some(HeadersOptions {
+            authorization: Some(authorization),
+        }) = &self.headers
+        {
+            let value = HeaderValue::from_str(authorization).map_err(|_| {
+                Error::InvalidInput("authorization header contains invalid characters".to_string())
+            })?;
+            headers_map.insert(AUTHORIZATION, value);
+        }
+        for (name, value) in &self.default_headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| Error::InvalidInput(format!("invalid header name {}", name)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| Error::InvalidInput(format!("header {} contains invalid characters", name)))?;
+            headers_map.insert(name, value);
+        }
+        Ok(headers_map)
+    }
+    /// Async reqwest client configured with these options.
+    pub(crate) fn reqwest_client(&self) -> Result<reqwest::Client, Error> {
+        configure_reqwest!(self, reqwest::ClientBuilder::new())
+    }
+    /// Blocking reqwest client configured with these options.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn reqwest_blocking_client(&self) -> Result<reqwest::blocking::Client, Error> {
+        configure_reqwest!(self, reqwest::blocking::ClientBuilder::new())
+    }
+}
@@ -0,0 +1,81 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// Answer of one server to a quorum read.
+#[derive(Debug)]
+pub struct QuorumVote {
+    /// Url of the server.
+    pub url: String,
+    /// Returned value in its Esplora JSON form, or the error returned by the server.
+    pub result: Result<serde_json::Value, Error>,
+}
+
+impl QuorumVote {
+    /// Returned value decoded as T, None if the server returned an error.
+    ///
+    /// Example :
+    /// ````rust
+    /// use esplora_api::data::blockstream::TxStatusFormat;
+    ///
+    /// fn confirmations(err: esplora_api::Error) {
+    ///     if let esplora_api::Error::QuorumNotReached { votes, .. } = err {
+    ///         for vote in votes {
+    ///             let status: Option<TxStatusFormat> = vote.value();
+    ///             println!("{}: {:?}", vote.url, status.map(|status| status.block_height));
+    ///         }
+    ///     }
+    /// }
+    /// ````
+    pub fn value<T: DeserializeOwned>(&self) -> Option<T> {
+        let value = self.result.as_ref().ok()?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// Check a quorum size against the number of servers.
+pub(crate) fn check_required(required: usize, servers: usize) -> Result<(), Error> {
+    if required == 0 || required > servers {
+        return Err(Error::InvalidInput(format!(
+            "quorum of {} cannot be reached with {} servers",
+            required, servers
+        )));
+    }
+    Ok(())
+}
+
+/// Return the value returned by at least `required` servers, or `Error::QuorumNotReached` listing every answer
+/// when no value or several different values reach `required` votes, e.g. a 2 against 2 split with a quorum of 2.
+pub(crate) fn tally<T: PartialEq + Serialize>(
+    required: usize,
+    results: Vec<(String, Result<T, Error>)>,
+) -> Result<T, Error> {
+    let answered = |answers: &[(String, Result<T, Error>)], value: &T| {
+        answers
+            .iter()
+            .filter(|(_, other)| matches!(other, Ok(other) if other == value))
+            .count()
+    };
+    // each distinct value is counted once, at its first answer
+    let agreeing: Vec<usize> = (0..results.len())
+        .filter(|index| match &results[*index].1 {
+            Ok(value) => answered(&results, value) >= required && answered(&results[..*index], value) == 0,
+            Err(_) => false,
+        })
+        .collect();
+    let mut results = results;
+    if let [index] = agreeing[..] {
+        if let (_, Ok(value)) = results.swap_remove(index) {
+            return Ok(value);
+        }
+    }
+    let votes = results
+        .into_iter()
+        .map(|(url, result)| QuorumVote {
+            url,
+            result: result.and_then(|value| serde_json::to_value(value).map_err(|err| Error::Parse(err.to_string()))),
+        })
+        .collect();
+    Err(Error::QuorumNotReached { required, votes })
+}
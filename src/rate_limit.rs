@@ -0,0 +1,93 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+
+/// Client-side token bucket limiting the rate of requests sent to Esplora.
+///
+/// The bucket holds up to `burst` requests and refills at `requests_per_second`.
+/// Once empty, the blocking client sleeps and the async client awaits until a request is allowed.
+/// The limiter is shared by all clones of a client, so tasks using clones of the same client share the same budget.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::RateLimit;
+///
+/// fn main() -> Result<(), esplora_api::Error> {
+///     // 5 requests per second on average, up to 10 at once
+///     let rate_limit = RateLimit::new(5.0, 10)?;
+///     Ok(())
+/// }
+/// ````
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests_per_second: f64,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// New rate limit, requests_per_second must be positive and burst at least 1.
+    pub fn new(requests_per_second: f64, burst: u32) -> Result<Self, Error> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(Error::InvalidInput(
+                "rate limit requests per second must be positive".to_string(),
+            ));
+        }
+        if burst == 0 {
+            return Err(Error::InvalidInput("rate limit burst must be at least 1".to_string()));
+        }
+        Ok(RateLimit {
+            requests_per_second,
+            burst,
+        })
+    }
+    /// Average number of requests allowed per second.
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+    /// Number of requests allowed at once.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+/// Token bucket state shared by clones of a client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens, negative when requests are already waiting.
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+    /// Take a token and return how long the caller must wait before sending its request.
+    pub(crate) fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.refilled_at = now;
+        bucket.tokens = (bucket.tokens + elapsed * self.limit.requests_per_second)
+            .min(f64::from(self.limit.burst))
+            - 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.limit.requests_per_second)
+        }
+    }
+}
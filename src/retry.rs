@@ -0,0 +1,96 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::transport::{Method, Request, Response};
+
+/// Retry policy applied by both clients when a call fails with a transient error.
+///
+/// Delays grow exponentially from `base_delay` up to `max_delay`. A numeric `Retry-After` header
+/// sent by Esplora replaces the computed delay, still capped by `max_delay`.
+/// `POST /tx` is only retried when `retry_post` is set, since a broadcast that timed out may already have been relayed.
+///
+/// Example :
+/// ````rust
+/// use std::time::Duration;
+/// use esplora_api::RetryPolicy;
+///
+/// let retry = RetryPolicy {
+///     max_attempts: 5,
+///     base_delay: Duration::from_millis(200),
+///     ..Default::default()
+/// };
+/// ````
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each following retry.
+    pub base_delay: Duration,
+    /// Upper bound of any delay, including `Retry-After`.
+    pub max_delay: Duration,
+    /// Randomize each backoff delay between half and all of its value.
+    pub jitter: bool,
+    /// HTTP status codes considered transient.
+    pub retry_statuses: Vec<u16>,
+    /// Retry when the transport fails to send the request or read the response.
+    pub retry_transport_errors: bool,
+    /// Also retry `POST /tx`.
+    pub retry_post: bool,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 500ms to 30s delays with jitter, retrying transport errors, 429 and 5xx gateway errors on GET requests.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_transport_errors: true,
+            retry_post: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait before attempting request again, None when outcome of attempt number `attempt` must be returned.
+    pub(crate) fn retry_delay(
+        &self,
+        request: &Request,
+        outcome: &Result<Response, Error>,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || (request.method == Method::Post && !self.retry_post) {
+            return None;
+        }
+        match outcome {
+            Ok(response) if self.retry_statuses.contains(&response.status) => {
+                Some(retry_after(response).map_or_else(|| self.backoff(attempt), |delay| delay.min(self.max_delay)))
+            }
+            Err(Error::Transport(_)) if self.retry_transport_errors => Some(self.backoff(attempt)),
+            _ => None,
+        }
+    }
+    /// Exponential backoff after attempt number `attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = self.base_delay.checked_mul(factor).unwrap_or(self.max_delay).min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+        let half = delay / 2;
+        let random = RandomState::new().build_hasher().finish();
+        half + Duration::from_nanos(random % (half.as_nanos() as u64).saturating_add(1))
+    }
+}
+
+/// `Retry-After` header expressed in seconds. HTTP dates are ignored.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .header("retry-after")
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
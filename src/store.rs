@@ -0,0 +1,189 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::endpoint::{Anchor, Endpoint};
+use crate::transport::Response;
+
+/// Persistent key-value storage of a client persistent cache, see
+/// [ApiClient::with_persistent_cache](crate::async_impl::ApiClient::with_persistent_cache).
+///
+/// Keys are Esplora route paths such as `/block/:hash/raw` and `index/:hash` entries,
+/// values are response bodies. Implementations must be safe to call from several threads.
+/// Debug is required so stores can be kept in [ClientOptions](crate::ClientOptions).
+pub trait CacheStore: Send + Sync + fmt::Debug {
+    /// Value stored for key, None if absent.
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+    /// Store value for key, replacing any previous value.
+    fn put(&self, key: &str, value: &[u8]) -> io::Result<()>;
+    /// Remove key, doing nothing if absent.
+    fn remove(&self, key: &str) -> io::Result<()>;
+}
+
+/// Store keeping each value in a file of a directory, named after its key.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::FsStore;
+///
+/// fn run() -> Result<(), esplora_api::Error> {
+///     let store = FsStore::new("/var/cache/esplora").expect("cache directory");
+///     let client = esplora_api::async_impl::ApiClient::new("https://blockstream.info/api", None)?.with_persistent_cache(store);
+///     Ok(())
+/// }
+/// ````
+#[derive(Debug)]
+pub struct FsStore {
+    dir: PathBuf,
+    temp_files: AtomicU64,
+}
+
+impl FsStore {
+    /// Store in dir, created if missing.
+    pub fn new(dir: impl AsRef<Path>) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(FsStore {
+            dir: dir.as_ref().to_path_buf(),
+            temp_files: AtomicU64::new(0),
+        })
+    }
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key
+            .trim_start_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        self.dir.join(name)
+    }
+}
+
+impl CacheStore for FsStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+    /// Write to a temporary file renamed over the key file, so readers never see a partial value.
+    fn put(&self, key: &str, value: &[u8]) -> io::Result<()> {
+        let path = self.path(key);
+        let temp = self.temp_files.fetch_add(1, Ordering::Relaxed);
+        let temp_path = path.with_extension(format!("tmp-{}-{}", std::process::id(), temp));
+        fs::write(&temp_path, value)?;
+        fs::rename(&temp_path, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+    }
+    fn remove(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Store backed by a sled tree. Requires the `sled` feature.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::SledStore;
+///
+/// fn run() -> Result<(), Box<dyn std::error::Error>> {
+///     let db = sled::open("/var/cache/esplora.sled")?;
+///     let client = esplora_api::async_impl::ApiClient::new("https://blockstream.info/api", None)?
+///         .with_persistent_cache(SledStore::new(db.open_tree("mainnet")?));
+///     Ok(())
+/// }
+/// ````
+#[cfg(feature = "sled")]
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+impl SledStore {
+    pub fn new(tree: sled::Tree) -> Self {
+        SledStore { tree }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl CacheStore for SledStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.tree.get(key)?.map(|value| value.to_vec()))
+    }
+    fn put(&self, key: &str, value: &[u8]) -> io::Result<()> {
+        self.tree.insert(key, value)?;
+        Ok(())
+    }
+    fn remove(&self, key: &str) -> io::Result<()> {
+        self.tree.remove(key)?;
+        Ok(())
+    }
+}
+
+/// Persistent cache of the immutable responses tied to a block, shared by the clones of a client.
+///
+/// Each block has an `index/:hash` entry listing the keys stored for it, so they can all be removed
+/// when the block leaves the best chain. Store failures are treated as cache misses.
+pub(crate) struct PersistentCache {
+    store: Arc<dyn CacheStore>,
+    /// Serializes index updates.
+    index: Mutex<()>,
+}
+
+impl PersistentCache {
+    pub(crate) fn new(store: Arc<dyn CacheStore>) -> Self {
+        PersistentCache {
+            store,
+            index: Mutex::new(()),
+        }
+    }
+    /// Stored response of the endpoint, if it is tied to a block.
+    pub(crate) fn get<T>(&self, endpoint: &Endpoint<T>) -> Option<Response> {
+        match endpoint.anchor() {
+            Anchor::Block(_) | Anchor::ConfirmingBlock => {}
+            Anchor::None | Anchor::BlockStatus(_) => return None,
+        }
+        let body = self.store.get(endpoint.path()).ok()??;
+        Some(Response::new(200, body))
+    }
+    /// Store the response of the endpoint if it is tied to a block.
+    pub(crate) fn observe<T>(&self, endpoint: &Endpoint<T>, response: &Response) {
+        if !response.is_success() {
+            return;
+        }
+        if let Some(hash) = endpoint.block(response) {
+            let _ = self.persist(&hash, endpoint.path(), &response.body);
+        }
+    }
+    /// Index key under the block before storing it, so it can always be invalidated.
+    fn persist(&self, hash: &str, key: &str, body: &[u8]) -> io::Result<()> {
+        {
+            let _index = self.index.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let index_key = format!("index/{}", hash);
+            let mut keys = self.store.get(&index_key)?.unwrap_or_default();
+            if !keys.split(|byte| *byte == b'\n').any(|indexed| indexed == key.as_bytes()) {
+                keys.extend_from_slice(key.as_bytes());
+                keys.push(b'\n');
+                self.store.put(&index_key, &keys)?;
+            }
+        }
+        self.store.put(key, body)
+    }
+    /// Remove every response stored for the block.
+    pub(crate) fn invalidate(&self, hash: &str) -> io::Result<()> {
+        let _index = self.index.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let index_key = format!("index/{}", hash);
+        let keys = self.store.get(&index_key)?.unwrap_or_default();
+        for key in keys.split(|byte| *byte == b'\n').filter(|key| !key.is_empty()) {
+            self.store.remove(&String::from_utf8_lossy(key))?;
+        }
+        self.store.remove(&index_key)
+    }
+}
@@ -0,0 +1,666 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde_json::{json, Value};
+
+use super::http::Reply;
+
+/// Transactions per page of `/block/:hash/txs`.
+const BLOCK_TXS_PAGE_SIZE: usize = 25;
+/// Confirmed transactions per page of the address history routes.
+const CHAIN_TXS_PAGE_SIZE: usize = 25;
+/// Mempool transactions returned by the address history routes.
+const MEMPOOL_TXS_LIMIT: usize = 50;
+/// Blocks per page of `/blocks/:start_height`.
+const BLOCKS_PAGE_SIZE: usize = 10;
+/// Timestamp of the genesis block, each block is 10 minutes after its parent.
+const GENESIS_TIME: u32 = 1_600_000_000;
+/// Value of the coinbase output of `mine`.
+const SUBSIDY: u64 = 5_000_000_000;
+
+/// Scripted in-memory chain served by a [MockServer](super::MockServer).
+///
+/// The chain starts with a genesis block. Transactions are created in the mempool with [send](MockChain::send)
+/// and confirmed by [mine](MockChain::mine), txids and hashes are deterministic, so a script always builds the same chain.
+/// Raw transactions and blocks, merkle proofs and scripthashes are placeholders unless set explicitly,
+/// they are not derived from real consensus data.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::testing::MockChain;
+///
+/// let mut chain = MockChain::new();
+/// let coinbase = chain.fund("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5", 100_000);
+/// let txid = chain.send(&[(&coinbase, 0)], &[("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk", 60_000)]);
+/// chain.mine();
+/// assert_eq!(chain.tip_height(), 2);
+/// ````
+#[derive(Debug, Clone)]
+pub struct MockChain {
+    /// Best chain, indexed by height.
+    blocks: Vec<Block>,
+    /// Blocks disconnected from the best chain.
+    orphans: Vec<Block>,
+    /// Transactions of the best chain and of the mempool.
+    txs: HashMap<String, Tx>,
+    /// Mempool txids, oldest first.
+    mempool: Vec<String>,
+    raw_txs: HashMap<String, Vec<u8>>,
+    raw_blocks: HashMap<String, Vec<u8>>,
+    fee_estimates: BTreeMap<u16, f64>,
+    /// Transactions accepted by `POST /tx`, by hex.
+    accepted: HashMap<String, String>,
+    broadcasts: Vec<String>,
+    ids: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    hash: String,
+    previous: Option<String>,
+    merkle_root: String,
+    height: u32,
+    txids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Tx {
+    txid: String,
+    /// Spent outpoints, empty for coinbase transactions.
+    inputs: Vec<(String, u32)>,
+    outputs: Vec<Output>,
+    fee: u64,
+    /// Hash of the confirming block, None in the mempool.
+    block: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Output {
+    address: Option<String>,
+    value: u64,
+}
+
+impl Default for MockChain {
+    fn default() -> Self {
+        MockChain::new()
+    }
+}
+
+impl MockChain {
+    /// Chain holding only its genesis block.
+    pub fn new() -> Self {
+        let mut chain = MockChain {
+            blocks: Vec::new(),
+            orphans: Vec::new(),
+            txs: HashMap::new(),
+            mempool: Vec::new(),
+            raw_txs: HashMap::new(),
+            raw_blocks: HashMap::new(),
+            fee_estimates: [(1, 25.0), (2, 20.0), (3, 15.0), (6, 10.0), (144, 1.0)].iter().cloned().collect(),
+            accepted: HashMap::new(),
+            broadcasts: Vec::new(),
+            ids: 0,
+        };
+        chain.mine();
+        chain
+    }
+    /// Mine a block confirming every mempool transaction, with a coinbase paying no address. Returns the block hash.
+    pub fn mine(&mut self) -> String {
+        self.mine_block(Output {
+            address: None,
+            value: SUBSIDY,
+        })
+    }
+    /// Mine a block confirming every mempool transaction, with a coinbase paying value to address. Returns the coinbase txid.
+    pub fn fund(&mut self, address: &str, value: u64) -> String {
+        self.mine_block(Output {
+            address: Some(address.to_string()),
+            value,
+        });
+        self.blocks[self.blocks.len() - 1].txids[0].clone()
+    }
+    /// Add to the mempool a transaction spending the outpoints and paying the outputs, the difference going to fees. Returns its txid.
+    ///
+    /// Panics if an outpoint is unknown, or if outputs exceed inputs.
+    pub fn send(&mut self, inputs: &[(&str, u32)], outputs: &[(&str, u64)]) -> String {
+        let funds: u64 = inputs
+            .iter()
+            .map(|(txid, vout)| {
+                self.output(txid, *vout)
+                    .unwrap_or_else(|| panic!("unknown outpoint {}:{}", txid, vout))
+                    .value
+            })
+            .sum();
+        let spent: u64 = outputs.iter().map(|(_, value)| value).sum();
+        assert!(spent <= funds, "outputs spend {} but inputs fund {}", spent, funds);
+        let txid = self.next_id();
+        let tx = Tx {
+            txid: txid.clone(),
+            inputs: inputs.iter().map(|(txid, vout)| (txid.to_string(), *vout)).collect(),
+            outputs: outputs
+                .iter()
+                .map(|(address, value)| Output {
+                    address: Some(address.to_string()),
+                    value: *value,
+                })
+                .collect(),
+            fee: funds - spent,
+            block: None,
+        };
+        self.txs.insert(txid.clone(), tx);
+        self.mempool.push(txid.clone());
+        txid
+    }
+    /// Disconnect the tip block, e.g. to script a reorg. Its transactions go back to the mempool, except its coinbase.
+    /// The block is still served, outside of the best chain. Returns its hash.
+    ///
+    /// Panics on the genesis block.
+    pub fn disconnect_tip(&mut self) -> String {
+        assert!(self.blocks.len() > 1, "cannot disconnect the genesis block");
+        let block = self.blocks.pop().expect("tip block");
+        self.txs.remove(&block.txids[0]);
+        let mut txids = block.txids[1..].to_vec();
+        for txid in &txids {
+            if let Some(tx) = self.txs.get_mut(txid) {
+                tx.block = None;
+            }
+        }
+        txids.append(&mut self.mempool);
+        self.mempool = txids;
+        let hash = block.hash.clone();
+        self.orphans.push(block);
+        hash
+    }
+    /// Serve bytes as the raw transaction of txid, e.g. a consensus-encoded transaction.
+    pub fn set_raw_tx(&mut self, txid: &str, raw: impl Into<Vec<u8>>) {
+        self.raw_txs.insert(txid.to_string(), raw.into());
+    }
+    /// Serve bytes as the raw block of hash.
+    pub fn set_raw_block(&mut self, hash: &str, raw: impl Into<Vec<u8>>) {
+        self.raw_blocks.insert(hash.to_string(), raw.into());
+    }
+    /// Replace the fee estimates, as (confirmation target, feerate in sat/vB) pairs.
+    pub fn set_fee_estimates(&mut self, estimates: &[(u16, f64)]) {
+        self.fee_estimates = estimates.iter().cloned().collect();
+    }
+    /// Accept the broadcast of hex_transaction, answering txid. Other broadcasts are rejected as undecodable.
+    pub fn accept_broadcast(&mut self, hex_transaction: &str, txid: &str) {
+        self.accepted.insert(hex_transaction.to_string(), txid.to_string());
+    }
+    /// Hex transactions received by `POST /tx`, accepted or not, oldest first.
+    pub fn broadcasts(&self) -> &[String] {
+        &self.broadcasts
+    }
+    /// Placeholder scripthash the mock serves for address under `/scripthash/:hash`.
+    pub fn script_hash(address: &str) -> String {
+        let seed = address
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+        id(seed)
+    }
+    pub fn tip_height(&self) -> u32 {
+        self.blocks.len() as u32 - 1
+    }
+    pub fn tip_hash(&self) -> &str {
+        &self.blocks[self.blocks.len() - 1].hash
+    }
+    /// Hash of the best chain block at height.
+    pub fn block_hash(&self, height: u32) -> Option<&str> {
+        self.blocks.get(height as usize).map(|block| block.hash.as_str())
+    }
+    /// Txids of a block, coinbase first.
+    pub fn block_txids(&self, hash: &str) -> Option<&[String]> {
+        self.block(hash).map(|block| block.txids.as_slice())
+    }
+    /// Txids of the mempool, oldest first.
+    pub fn mempool_txids(&self) -> &[String] {
+        &self.mempool
+    }
+
+    fn mine_block(&mut self, reward: Output) -> String {
+        let height = self.blocks.len() as u32;
+        let (hash, merkle_root, coinbase) = (self.next_id(), self.next_id(), self.next_id());
+        let fees: u64 = self.mempool.iter().map(|txid| self.txs[txid].fee).sum();
+        self.txs.insert(
+            coinbase.clone(),
+            Tx {
+                txid: coinbase.clone(),
+                inputs: Vec::new(),
+                outputs: vec![Output {
+                    value: reward.value + fees,
+                    ..reward
+                }],
+                fee: 0,
+                block: None,
+            },
+        );
+        let mut txids = vec![coinbase];
+        txids.append(&mut self.mempool);
+        for txid in &txids {
+            self.txs.get_mut(txid).expect("mined transaction").block = Some(hash.clone());
+        }
+        self.blocks.push(Block {
+            hash: hash.clone(),
+            previous: self.blocks.last().map(|block| block.hash.clone()),
+            merkle_root,
+            height,
+            txids,
+        });
+        hash
+    }
+    fn next_id(&mut self) -> String {
+        self.ids += 1;
+        id(self.ids)
+    }
+    fn block(&self, hash: &str) -> Option<&Block> {
+        self.blocks.iter().chain(&self.orphans).find(|block| block.hash == hash)
+    }
+    fn in_best_chain(&self, block: &Block) -> bool {
+        self.blocks.get(block.height as usize).is_some_and(|best| best.hash == block.hash)
+    }
+    fn output(&self, txid: &str, vout: u32) -> Option<&Output> {
+        self.txs.get(txid)?.outputs.get(vout as usize)
+    }
+    /// Transaction spending an outpoint and the index of its input.
+    fn spender(&self, txid: &str, vout: u32) -> Option<(&Tx, usize)> {
+        self.txs.values().find_map(|tx| {
+            tx.inputs
+                .iter()
+                .position(|(spent, spent_vout)| spent == txid && *spent_vout == vout)
+                .map(|vin| (tx, vin))
+        })
+    }
+    /// Confirmed transactions, newest first.
+    fn chain_txs(&self) -> impl Iterator<Item = &Tx> {
+        self.blocks
+            .iter()
+            .rev()
+            .flat_map(|block| block.txids.iter().rev())
+            .map(move |txid| &self.txs[txid])
+    }
+    /// Mempool transactions, newest first.
+    fn mempool_txs(&self) -> impl Iterator<Item = &Tx> {
+        self.mempool.iter().rev().map(move |txid| &self.txs[txid])
+    }
+    fn involves(&self, tx: &Tx, address: &str) -> bool {
+        tx.outputs.iter().any(|output| output.address.as_deref() == Some(address))
+            || tx
+                .inputs
+                .iter()
+                .any(|(txid, vout)| self.output(txid, *vout).is_some_and(|output| output.address.as_deref() == Some(address)))
+    }
+    /// Address whose placeholder scripthash is scripthash.
+    fn script_hash_address(&self, scripthash: &str) -> Option<String> {
+        self.txs
+            .values()
+            .flat_map(|tx| &tx.outputs)
+            .filter_map(|output| output.address.as_ref())
+            .find(|address| MockChain::script_hash(address) == scripthash)
+            .cloned()
+    }
+    fn size(tx: &Tx) -> u64 {
+        60 + 148 * tx.inputs.len().max(1) as u64 + 34 * tx.outputs.len() as u64
+    }
+
+    fn block_json(&self, block: &Block) -> Value {
+        let size: u64 = 80 + block.txids.iter().filter_map(|txid| self.txs.get(txid)).map(MockChain::size).sum::<u64>();
+        let timestamp = GENESIS_TIME + block.height * 600;
+        json!({
+            "id": block.hash,
+            "height": block.height,
+            "version": 536_870_912,
+            "timestamp": timestamp,
+            "tx_count": block.txids.len(),
+            "size": size,
+            "weight": size * 4,
+            "merkle_root": block.merkle_root,
+            "previousblockhash": block.previous,
+            "mediantime": timestamp.saturating_sub(3000),
+            "nonce": 0,
+            "bits": 486_604_799,
+            "difficulty": 1.0,
+        })
+    }
+    fn block_status_json(&self, block: &Block) -> Value {
+        if !self.in_best_chain(block) {
+            return json!({ "in_best_chain": false });
+        }
+        let mut status = json!({ "in_best_chain": true, "height": block.height });
+        if let Some(next) = self.blocks.get(block.height as usize + 1) {
+            status["next_best"] = json!(next.hash);
+        }
+        status
+    }
+    fn status_json(&self, tx: &Tx) -> Value {
+        match tx.block.as_ref().and_then(|hash| self.block(hash)) {
+            Some(block) => json!({
+                "confirmed": true,
+                "block_height": block.height,
+                "block_hash": block.hash,
+                "block_time": GENESIS_TIME + block.height * 600,
+            }),
+            None => json!({ "confirmed": false }),
+        }
+    }
+    fn output_json(output: &Output) -> Value {
+        let script = output.address.as_deref().map_or_else(|| "6a".to_string(), |address| to_hex(address.as_bytes()));
+        let mut vout = json!({
+            "scriptpubkey": script,
+            "scriptpubkey_asm": "",
+            "scriptpubkey_type": if output.address.is_some() { "unknown" } else { "op_return" },
+            "value": output.value,
+        });
+        if let Some(address) = &output.address {
+            vout["scriptpubkey_address"] = json!(address);
+        }
+        vout
+    }
+    fn tx_json(&self, tx: &Tx, with_status: bool) -> Value {
+        let vin: Vec<Value> = if tx.inputs.is_empty() {
+            vec![json!({
+                "txid": "0".repeat(64),
+                "vout": u32::MAX,
+                "prevout": null,
+                "scriptsig": "00",
+                "scriptsig_asm": "OP_0",
+                "is_coinbase": true,
+                "sequence": u32::MAX,
+            })]
+        } else {
+            tx.inputs
+                .iter()
+                .map(|(txid, vout)| {
+                    json!({
+                        "txid": txid,
+                        "vout": vout,
+                        "prevout": self.output(txid, *vout).map(MockChain::output_json),
+                        "scriptsig": "",
+                        "scriptsig_asm": "",
+                        "is_coinbase": false,
+                        "sequence": u32::MAX - 1,
+                    })
+                })
+                .collect()
+        };
+        let size = MockChain::size(tx);
+        let mut json = json!({
+            "txid": tx.txid,
+            "version": 2,
+            "locktime": 0,
+            "vin": vin,
+            "vout": tx.outputs.iter().map(MockChain::output_json).collect::<Vec<_>>(),
+            "size": size,
+            "weight": size * 4,
+            "fee": tx.fee,
+        });
+        if with_status {
+            json["status"] = self.status_json(tx);
+        }
+        json
+    }
+    fn outspend_json(&self, txid: &str, vout: u32) -> Value {
+        match self.spender(txid, vout) {
+            Some((tx, vin)) => json!({
+                "spent": true,
+                "txid": tx.txid,
+                "vin": vin,
+                "status": self.status_json(tx),
+            }),
+            None => json!({ "spent": false }),
+        }
+    }
+    fn stats_json<'a>(&self, txs: impl Iterator<Item = &'a Tx>, address: &str) -> Value {
+        let (mut funded, mut funded_sum, mut spent, mut spent_sum, mut tx_count) = (0, 0, 0, 0, 0);
+        for tx in txs.filter(|tx| self.involves(tx, address)) {
+            tx_count += 1;
+            for output in tx.outputs.iter().filter(|output| output.address.as_deref() == Some(address)) {
+                funded += 1;
+                funded_sum += output.value;
+            }
+            for (txid, vout) in &tx.inputs {
+                if let Some(output) = self.output(txid, *vout).filter(|output| output.address.as_deref() == Some(address)) {
+                    spent += 1;
+                    spent_sum += output.value;
+                }
+            }
+        }
+        json!({
+            "funded_txo_count": funded,
+            "funded_txo_sum": funded_sum,
+            "spent_txo_count": spent,
+            "spent_txo_sum": spent_sum,
+            "tx_count": tx_count,
+        })
+    }
+    fn mempool_json(&self) -> Value {
+        let mut histogram: Vec<(f64, u64)> = self
+            .mempool_txs()
+            .map(|tx| (tx.fee as f64 / MockChain::size(tx) as f64, MockChain::size(tx)))
+            .collect();
+        histogram.sort_by(|a, b| b.0.total_cmp(&a.0));
+        json!({
+            "count": self.mempool.len(),
+            "vsize": histogram.iter().map(|(_, vsize)| vsize).sum::<u64>(),
+            "total_fee": self.mempool_txs().map(|tx| tx.fee).sum::<u64>(),
+            "fee_histogram": histogram,
+        })
+    }
+
+    /// Answer a request for path, relative to the server url.
+    pub(super) fn respond(&mut self, method: &str, path: &str, body: &str) -> Reply {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["tx"]) => self.broadcast(body.trim()),
+            ("GET", ["block", hash, rest @ ..]) => match self.block(hash) {
+                Some(block) => self.block_route(block, rest),
+                None => Reply::error(404, "Block not found"),
+            },
+            ("GET", ["block-height", height]) => {
+                match height.parse().ok().and_then(|height| self.block_hash(height)) {
+                    Some(hash) => Reply::text(hash),
+                    None => Reply::error(404, "Block not found"),
+                }
+            }
+            ("GET", ["blocks", "tip", "height"]) => Reply::text(&self.tip_height().to_string()),
+            ("GET", ["blocks", "tip", "hash"]) => Reply::text(self.tip_hash()),
+            ("GET", ["blocks"]) => self.blocks_page(self.tip_height()),
+            ("GET", ["blocks", start]) => match start.parse() {
+                Ok(start) => self.blocks_page(start),
+                Err(_) => Reply::error(400, "Invalid height"),
+            },
+            ("GET", ["tx", txid, rest @ ..]) => match self.txs.get(*txid) {
+                Some(tx) => self.tx_route(tx, rest),
+                None => Reply::error(404, "Transaction not found"),
+            },
+            ("GET", ["address", address, rest @ ..]) => self.address_route(address, "address", address, rest),
+            ("GET", ["scripthash", scripthash, rest @ ..]) => {
+                let address = self.script_hash_address(scripthash).unwrap_or_default();
+                self.address_route(&address, "scripthash", scripthash, rest)
+            }
+            ("GET", ["address-prefix", prefix]) => {
+                let addresses: BTreeSet<&String> = self
+                    .txs
+                    .values()
+                    .flat_map(|tx| &tx.outputs)
+                    .filter_map(|output| output.address.as_ref())
+                    .filter(|address| address.starts_with(prefix))
+                    .collect();
+                Reply::json(json!(addresses.into_iter().take(10).collect::<Vec<_>>()))
+            }
+            ("GET", ["mempool"]) => Reply::json(self.mempool_json()),
+            ("GET", ["mempool", "txids"]) => Reply::json(json!(self.mempool)),
+            ("GET", ["mempool", "recent"]) => Reply::json(json!(self
+                .mempool_txs()
+                .take(10)
+                .map(|tx| json!({
+                    "txid": tx.txid,
+                    "fee": tx.fee,
+                    "vsize": MockChain::size(tx),
+                    "value": tx.outputs.iter().map(|output| output.value).sum::<u64>(),
+                }))
+                .collect::<Vec<_>>())),
+            ("GET", ["fee-estimates"]) => Reply::json(json!(self
+                .fee_estimates
+                .iter()
+                .map(|(target, feerate)| (target.to_string(), *feerate))
+                .collect::<BTreeMap<_, _>>())),
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+    fn broadcast(&mut self, hex_transaction: &str) -> Reply {
+        self.broadcasts.push(hex_transaction.to_string());
+        match self.accepted.get(hex_transaction) {
+            Some(txid) => Reply::text(txid),
+            None => Reply::error(
+                400,
+                r#"sendrawtransaction RPC error: {"code":-22,"message":"TX decode failed"}"#,
+            ),
+        }
+    }
+    fn block_route(&self, block: &Block, rest: &[&str]) -> Reply {
+        match rest {
+            [] => Reply::json(self.block_json(block)),
+            ["status"] => Reply::json(self.block_status_json(block)),
+            ["txids"] => Reply::json(json!(block.txids)),
+            ["txid", index] => match index.parse::<usize>().ok().and_then(|index| block.txids.get(index)) {
+                Some(txid) => Reply::text(txid),
+                None => Reply::error(404, "Transaction not found"),
+            },
+            ["txs"] => self.block_txs_page(block, "0"),
+            ["txs", start] => self.block_txs_page(block, start),
+            ["raw"] => match self.raw_blocks.get(&block.hash) {
+                Some(raw) => Reply::bytes(raw.clone()),
+                None => Reply::bytes(from_hex(&block.hash)),
+            },
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+    fn block_txs_page(&self, block: &Block, start: &str) -> Reply {
+        let start = match start.parse::<usize>() {
+            Ok(start) if start % BLOCK_TXS_PAGE_SIZE != 0 => {
+                return Reply::error(400, "start index must be a multipication of 25")
+            }
+            Ok(start) if start >= block.txids.len() => return Reply::error(400, "start index out of range"),
+            Ok(start) => start,
+            Err(_) => return Reply::error(400, "Invalid start index"),
+        };
+        let txs: Vec<Value> = block.txids[start..]
+            .iter()
+            .take(BLOCK_TXS_PAGE_SIZE)
+            .filter_map(|txid| self.txs.get(txid))
+            .map(|tx| self.tx_json(tx, false))
+            .collect();
+        Reply::json(json!(txs))
+    }
+    fn blocks_page(&self, start: u32) -> Reply {
+        let start = start.min(self.tip_height()) as usize;
+        let blocks: Vec<Value> = self.blocks[..=start]
+            .iter()
+            .rev()
+            .take(BLOCKS_PAGE_SIZE)
+            .map(|block| self.block_json(block))
+            .collect();
+        Reply::json(json!(blocks))
+    }
+    fn tx_route(&self, tx: &Tx, rest: &[&str]) -> Reply {
+        let raw = || self.raw_txs.get(&tx.txid).cloned().unwrap_or_else(|| from_hex(&tx.txid));
+        let block = tx.block.as_ref().and_then(|hash| self.block(hash));
+        match rest {
+            [] => Reply::json(self.tx_json(tx, true)),
+            ["status"] => Reply::json(self.status_json(tx)),
+            ["raw"] => Reply::bytes(raw()),
+            ["hex"] => Reply::text(&to_hex(&raw())),
+            ["merkle-proof"] => match block {
+                Some(block) => Reply::json(json!({
+                    "block_height": block.height,
+                    "merkle": [],
+                    "pos": block.txids.iter().position(|txid| *txid == tx.txid),
+                })),
+                None => Reply::error(404, "Transaction not found"),
+            },
+            ["merkleblock-proof"] => match block {
+                Some(block) => Reply::text(&format!("{}{}", block.hash, tx.txid)),
+                None => Reply::error(404, "Transaction not found"),
+            },
+            ["outspends"] => Reply::json(json!((0..tx.outputs.len() as u32)
+                .map(|vout| self.outspend_json(&tx.txid, vout))
+                .collect::<Vec<_>>())),
+            ["outspend", vout] => match vout.parse() {
+                Ok(vout) => Reply::json(self.outspend_json(&tx.txid, vout)),
+                Err(_) => Reply::error(400, "Invalid vout"),
+            },
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+    /// Routes of `/address/:address` and `/scripthash/:hash`, key being the address or the scripthash.
+    fn address_route(&self, address: &str, kind: &str, key: &str, rest: &[&str]) -> Reply {
+        let involved = |tx: &&Tx| self.involves(tx, address);
+        let page = |txs: Vec<&Tx>| Reply::json(json!(txs.into_iter().map(|tx| self.tx_json(tx, true)).collect::<Vec<_>>()));
+        match rest {
+            [] => {
+                let mut info = json!({
+                    "chain_stats": self.stats_json(self.chain_txs(), address),
+                    "mempool_stats": self.stats_json(self.mempool_txs(), address),
+                });
+                info[kind] = json!(key);
+                Reply::json(info)
+            }
+            ["txs"] => page(
+                self.mempool_txs()
+                    .filter(involved)
+                    .take(MEMPOOL_TXS_LIMIT)
+                    .chain(self.chain_txs().filter(involved).take(CHAIN_TXS_PAGE_SIZE))
+                    .collect(),
+            ),
+            ["txs", "mempool"] => page(self.mempool_txs().filter(involved).take(MEMPOOL_TXS_LIMIT).collect()),
+            ["txs", "chain"] => page(self.chain_txs().filter(involved).take(CHAIN_TXS_PAGE_SIZE).collect()),
+            ["txs", "chain", last_seen] => {
+                let mut txs = self.chain_txs().filter(involved).skip_while(|tx| tx.txid != *last_seen);
+                let found = txs.next().is_some();
+                page(if found { txs.take(CHAIN_TXS_PAGE_SIZE).collect() } else { Vec::new() })
+            }
+            ["utxo"] => {
+                let utxos: Vec<Value> = self
+                    .mempool_txs()
+                    .chain(self.chain_txs())
+                    .flat_map(|tx| tx.outputs.iter().enumerate().map(move |(vout, output)| (tx, vout as u32, output)))
+                    .filter(|(tx, vout, output)| {
+                        output.address.as_deref() == Some(address) && self.spender(&tx.txid, *vout).is_none()
+                    })
+                    .map(|(tx, vout, output)| {
+                        json!({
+                            "txid": tx.txid,
+                            "vout": vout,
+                            "status": self.status_json(tx),
+                            "value": output.value,
+                        })
+                    })
+                    .collect();
+                Reply::json(json!(utxos))
+            }
+            _ => Reply::error(404, "Not found"),
+        }
+    }
+}
+
+/// Deterministic 64 hex characters id derived from seed with splitmix64.
+fn id(seed: u64) -> String {
+    (1..=4u64)
+        .map(|round| {
+            let mut z = seed.wrapping_add(round.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            format!("{:016x}", z ^ (z >> 31))
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len() / 2)
+        .filter_map(|i| u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok())
+        .collect()
+}
@@ -0,0 +1,98 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+/// Response of the mock server.
+#[derive(Debug, Clone)]
+pub(super) struct Reply {
+    pub(super) status: u16,
+    pub(super) headers: Vec<(String, String)>,
+    pub(super) body: Vec<u8>,
+}
+
+impl Reply {
+    pub(super) fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Reply {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+    pub(super) fn json(value: serde_json::Value) -> Self {
+        Reply::new(200, "application/json", value.to_string())
+    }
+    pub(super) fn text(text: &str) -> Self {
+        Reply::new(200, "text/plain", text)
+    }
+    pub(super) fn bytes(bytes: Vec<u8>) -> Self {
+        Reply::new(200, "application/octet-stream", bytes)
+    }
+    /// Plain-text error message, as returned by Esplora.
+    pub(super) fn error(status: u16, message: &str) -> Self {
+        Reply::new(status, "text/plain", message)
+    }
+}
+
+/// Request read from a connection.
+pub(super) struct Incoming {
+    pub(super) method: String,
+    /// Path and query of the request target.
+    pub(super) path: String,
+    pub(super) body: String,
+}
+
+/// Read one HTTP/1.1 request.
+pub(super) fn read_request(stream: &TcpStream) -> io::Result<Incoming> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed request line")),
+    };
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Incoming {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Write reply and close the connection.
+pub(super) fn write_reply(mut stream: &TcpStream, reply: &Reply) -> io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} {}\r\n", reply.status, reason(reply.status));
+    for (name, value) in &reply.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", reply.body.len()));
+    let mut response = head.into_bytes();
+    response.extend_from_slice(&reply.body);
+    stream.write_all(&response)?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
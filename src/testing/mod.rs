@@ -0,0 +1,192 @@
+//! In-process mock Esplora server for offline tests. Requires the `testing` feature.
+//!
+//! [MockServer](MockServer) serves the Esplora routes over HTTP on a local port from a scripted
+//! [MockChain](MockChain), so any client or transport can be tested without network.
+//! Faults (error statuses, latency, 429 with `Retry-After`) can be injected to exercise retries,
+//! failover and rate limiting. Asset routes are not modelled and answer 404 unless scripted with
+//! [MockServer::set_response](MockServer::set_response).
+//!
+//! [Recorder](Recorder) and [Replay](Replay) record real Esplora exchanges to a fixture file once
+//! and replay them deterministically, e.g. in CI.
+//!
+//! Example :
+//! ````rust
+//! use esplora_api::testing::{MockChain, MockServer};
+//!
+//! #[tokio::main]
+//! async fn run() -> Result<(), esplora_api::Error> {
+//!     let mut chain = MockChain::new();
+//!     let txid = chain.fund("n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk", 50_000);
+//!     let server = MockServer::start(chain).expect("mock server");
+//!     let client = esplora_api::async_impl::ApiClient::new(&server.url(), None)?;
+//!     let tx = client.get_tx(&txid).await?;
+//!     assert_eq!(tx.vout[0].value, Some(50_000));
+//!     Ok(())
+//! }
+//! ````
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+mod chain;
+mod http;
+mod vcr;
+
+pub use self::chain::MockChain;
+pub use self::vcr::{Recorder, Replay};
+use self::http::Reply;
+
+/// Mock Esplora server listening on a local port, stopped when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct State {
+    chain: Mutex<MockChain>,
+    faults: Mutex<Faults>,
+    requests: Mutex<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct Faults {
+    latency: Duration,
+    /// Replies of the next requests, whatever their path.
+    next: VecDeque<Reply>,
+    /// Replies of the requests whose path starts with a prefix.
+    prefixes: Vec<(String, Reply)>,
+    /// Replies of the requests to an exact path, query included.
+    paths: HashMap<String, Reply>,
+}
+
+impl MockServer {
+    /// Serve chain on a free port of 127.0.0.1.
+    pub fn start(chain: MockChain) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(State {
+            chain: Mutex::new(chain),
+            faults: Mutex::new(Faults::default()),
+            requests: Mutex::new(Vec::new()),
+        });
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let (state, stop) = (state.clone(), stop.clone());
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let state = state.clone();
+                        thread::spawn(move || state.serve(stream));
+                    }
+                }
+            })
+        };
+        Ok(MockServer {
+            addr,
+            state,
+            stop,
+            handle: Some(handle),
+        })
+    }
+    /// Base url of the server, to give to a client.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+    /// Served chain, which can be changed while the server runs, e.g. to mine blocks.
+    pub fn chain(&self) -> MutexGuard<'_, MockChain> {
+        lock(&self.state.chain)
+    }
+    /// Requests received, as `METHOD /path`, oldest first.
+    pub fn requests(&self) -> Vec<String> {
+        lock(&self.state.requests).clone()
+    }
+    /// Delay every response by latency.
+    pub fn set_latency(&self, latency: Duration) {
+        self.faults().latency = latency;
+    }
+    /// Answer the next count requests with status.
+    pub fn fail_next(&self, count: usize, status: u16) {
+        let reply = Reply::error(status, "mock failure");
+        self.faults().next.extend(std::iter::repeat_n(reply, count));
+    }
+    /// Answer the next count requests with 429 Too Many Requests and a `Retry-After` header.
+    pub fn throttle_next(&self, count: usize, retry_after: Duration) {
+        let mut reply = Reply::error(429, "Too Many Requests");
+        reply.headers.push(("Retry-After".to_string(), retry_after.as_secs().to_string()));
+        self.faults().next.extend(std::iter::repeat_n(reply, count));
+    }
+    /// Answer every request whose path starts with prefix with status, e.g. `/tx/` or `/` for all routes.
+    pub fn fail_path(&self, prefix: &str, status: u16) {
+        self.faults().prefixes.push((prefix.to_string(), Reply::error(status, "mock failure")));
+    }
+    /// Answer requests to path, query included, with status and body instead of the chain, e.g. to script asset routes.
+    pub fn set_response(&self, path: &str, status: u16, body: impl Into<Vec<u8>>) {
+        let reply = Reply::new(status, "application/json", body);
+        self.faults().paths.insert(path.to_string(), reply);
+    }
+    /// Remove latency, injected failures and scripted responses.
+    pub fn clear_faults(&self) {
+        *self.faults() = Faults::default();
+    }
+    fn faults(&self) -> MutexGuard<'_, Faults> {
+        lock(&self.state.faults)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // wake the accept loop up so it sees the stop flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl State {
+    fn serve(&self, stream: TcpStream) {
+        let request = match http::read_request(&stream) {
+            Ok(request) => request,
+            Err(_) => return,
+        };
+        lock(&self.requests).push(format!("{} {}", request.method, request.path));
+        let (latency, fault) = {
+            let mut faults = lock(&self.faults);
+            let fault = match faults.next.pop_front() {
+                Some(reply) => Some(reply),
+                None => {
+                    let path = request.path.split('?').next().unwrap_or_default();
+                    faults
+                        .prefixes
+                        .iter()
+                        .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+                        .map(|(_, reply)| reply.clone())
+                        .or_else(|| faults.paths.get(&request.path).cloned())
+                }
+            };
+            (faults.latency, fault)
+        };
+        if !latency.is_zero() {
+            thread::sleep(latency);
+        }
+        let reply = fault
+            .unwrap_or_else(|| lock(&self.chain).respond(&request.method, &request.path, &request.body));
+        let _ = http::write_reply(&stream, &reply);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
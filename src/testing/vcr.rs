@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::async_impl::{EsploraTransport, TransportFuture};
+use crate::error::Error;
+use crate::transport::{Method, Request, Response};
+
+/// Exchange stored in a fixture file. Bodies are stored as text, or as hex when they are not UTF-8.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct Interaction {
+    method: String,
+    /// Url path and query, the host is not recorded.
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_body: Option<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_hex: Option<String>,
+}
+
+impl Interaction {
+    fn new(request: &Request, response: &Response) -> Self {
+        let (body, body_hex) = match String::from_utf8(response.body.clone()) {
+            Ok(body) => (Some(body), None),
+            Err(_) => (None, Some(response.body.iter().map(|byte| format!("{:02x}", byte)).collect())),
+        };
+        Interaction {
+            method: method_name(request.method).to_string(),
+            path: url_path(&request.url).to_string(),
+            request_body: request.body.clone(),
+            status: response.status,
+            headers: response.headers.clone(),
+            body,
+            body_hex,
+        }
+    }
+    fn matches(&self, request: &Request) -> bool {
+        self.method == method_name(request.method)
+            && self.path == url_path(&request.url)
+            && self.request_body == request.body
+    }
+    fn response(&self) -> Response {
+        let body = match (&self.body, &self.body_hex) {
+            (Some(body), _) => body.clone().into_bytes(),
+            (None, Some(hex)) => (0..hex.len() / 2)
+                .filter_map(|i| u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok())
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+        Response {
+            status: self.status,
+            headers: self.headers.clone(),
+            body,
+        }
+    }
+}
+
+/// Transport recording the exchanges of another transport to a fixture file, to be replayed with [Replay](Replay).
+///
+/// The file is rewritten after each exchange, so it is complete even if the test stops early.
+/// Exchanges that failed in the wrapped transport are not recorded.
+/// Works with the async and blocking clients, wrapping a transport of the same kind.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::testing::Recorder;
+///
+/// #[tokio::main]
+/// async fn run() -> Result<(), esplora_api::Error> {
+///     let recorder = Recorder::new(reqwest::Client::new(), "tests/fixtures/tip.json");
+///     let client = esplora_api::async_impl::ApiClient::with_transport("https://blockstream.info/testnet/api/", recorder)?;
+///     client.get_blocks_tip_height().await?;
+///     Ok(())
+/// }
+/// ````
+#[derive(Debug)]
+pub struct Recorder<T> {
+    inner: T,
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl<T> Recorder<T> {
+    /// Record the exchanges of inner to path, replacing any previous recording.
+    pub fn new(inner: T, path: impl Into<PathBuf>) -> Self {
+        Recorder {
+            inner,
+            path: path.into(),
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+    fn record(&self, request: &Request, response: Response) -> Result<Response, Error> {
+        let mut interactions = self.interactions.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        interactions.push(Interaction::new(request, &response));
+        let json = serde_json::to_vec_pretty(&*interactions).map_err(|err| Error::Transport(Box::new(err)))?;
+        fs::write(&self.path, json).map_err(|err| Error::Transport(Box::new(err)))?;
+        Ok(response)
+    }
+}
+
+impl<T: EsploraTransport> EsploraTransport for Recorder<T> {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let response = self.inner.send(request).await?;
+            self.record(request, response)
+        })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T: crate::blocking::EsploraTransport> crate::blocking::EsploraTransport for Recorder<T> {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        let response = self.inner.send(request)?;
+        self.record(request, response)
+    }
+}
+
+/// Transport answering requests from a fixture file written by [Recorder](Recorder), without network.
+///
+/// Requests are matched on their method, url path and body, whatever the host of the client url.
+/// Identical requests get their recorded responses in order, the last one being repeated.
+/// A request missing from the recording fails with `Error::InvalidInput`, naming the request and the fixture file.
+/// This error is neither retried nor failed over, so the call fails instead of the client working around it.
+/// Works with the async and blocking clients.
+///
+/// Example :
+/// ````rust
+/// use esplora_api::testing::Replay;
+///
+/// #[tokio::main]
+/// async fn run() -> Result<(), esplora_api::Error> {
+///     let replay = Replay::load("tests/fixtures/tip.json").expect("fixture");
+///     let client = esplora_api::async_impl::ApiClient::with_transport("https://blockstream.info/testnet/api/", replay)?;
+///     let height = client.get_blocks_tip_height().await?;
+///     Ok(())
+/// }
+/// ````
+#[derive(Debug)]
+pub struct Replay {
+    path: PathBuf,
+    interactions: Vec<Interaction>,
+    /// Number of responses already replayed per request, by index of its first recorded exchange.
+    played: Mutex<HashMap<usize, usize>>,
+}
+
+impl Replay {
+    /// Replay the recording of path.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read(path.as_ref())?;
+        let interactions =
+            serde_json::from_slice(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Replay {
+            path: path.as_ref().to_path_buf(),
+            interactions,
+            played: Mutex::new(HashMap::new()),
+        })
+    }
+    fn play(&self, request: &Request) -> Result<Response, Error> {
+        let recorded: Vec<usize> = (0..self.interactions.len())
+            .filter(|index| self.interactions[*index].matches(request))
+            .collect();
+        if recorded.is_empty() {
+            return Err(Error::InvalidInput(format!(
+                "no response recorded for {} {} in {}",
+                method_name(request.method),
+                url_path(&request.url),
+                self.path.display()
+            )));
+        }
+        let mut played = self.played.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let count = played.entry(recorded[0]).or_insert(0);
+        let index = recorded[(*count).min(recorded.len() - 1)];
+        *count += 1;
+        Ok(self.interactions[index].response())
+    }
+}
+
+impl EsploraTransport for Replay {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        let response = self.play(request);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl crate::blocking::EsploraTransport for Replay {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        self.play(request)
+    }
+}
+
+fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::Get => "GET",
+        Method::Post => "POST",
+    }
+}
+
+/// Path and query of url.
+fn url_path(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.find('/').map_or("/", |start| &rest[start..])
+}
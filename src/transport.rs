@@ -0,0 +1,57 @@
+//! Transport agnostic request and response types.
+//!
+//! Clients describe every Esplora call as a [Request](Request) and hand it to a transport,
+//! see [async_impl::EsploraTransport](crate::async_impl::EsploraTransport) and `blocking::EsploraTransport`.
+//! A transport only has to perform the HTTP exchange and return the raw [Response](Response),
+//! status checks and decoding are done by the client.
+use std::time::Duration;
+
+/// HTTP method of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// Request sent by a client to its transport.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Request {
+    pub method: Method,
+    /// Full request url, base url of the client followed by the endpoint path.
+    pub url: String,
+    /// Plain-text request body, only used by `POST /tx`.
+    pub body: Option<String>,
+    /// Total timeout of the request set on the client, overriding the transport default.
+    pub timeout: Option<Duration>,
+}
+
+/// Raw response returned by a transport, whatever its status code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// Response headers, names are lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// New response without headers.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+    /// Value of the first header named `name`, case insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+    /// Whether the status code is 2xx.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
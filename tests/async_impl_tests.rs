@@ -1,10 +1,48 @@
+use std::time::Duration;
+
 use esplora_api::async_impl::{ApiClient, ClientOptions, HeadersOptions};
-use reqwest;
+use esplora_api::testing::{MockChain, MockServer, Recorder, Replay};
+use esplora_api::{Error, RetryPolicy};
 use reqwest::header;
 static ENDPOINT_URL: &str = "https://blockstream.info/testnet/api/";
+static ADDRESS: &str = "2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5";
+static PAYEE: &str = "n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk";
+static UTXO_ADDRESS: &str = "2NDcM3CGUTwqFL7y8BSBJTYJ9kToeXawkUF";
+static COINBASE_HEX: &str = "010000000001010000000000000000000000000000000000000000000000000000000000000000ffffffff2003220d1c04d6d37c5f0877fffb9a4b3500000d2f6e6f64655374726174756d2f00000000030000000000000000266a24aa21a9ed61dc942663feda48033d1026d2fa8acf0f098870202c541bffa7771e8dc51e159b0e2801000000001976a914dfdf4d53296fac595dc33d8ac7216ba516b8dcc588ac8ffd0200000000001976a914bfcc245931cbad63d09f62df43bcab989991014e88ac0120000000000000000000000000000000000000000000000000000000000000000000000000";
+
+/// Mock Esplora server and the ids of its scripted chain.
+struct Mock {
+    server: MockServer,
+    client: ApiClient,
+    /// Block at height 3, holding 30 transactions.
+    block: String,
+    /// Transaction confirmed at height 2, its output 0 paying PAYEE and its output 1 spent in the mempool.
+    tx: String,
+}
 
-fn default_client() -> ApiClient {
-    return ApiClient::new(ENDPOINT_URL, None).unwrap();
+/// Chain of 5 blocks where ADDRESS pays PAYEE at height 2, then UTXO_ADDRESS pays PAYEE 29 times at height 3,
+/// ADDRESS paying itself in the mempool.
+fn mock() -> Mock {
+    let mut chain = MockChain::new();
+    let funding = chain.fund(ADDRESS, 1_000_000);
+    let tx = chain.send(&[(&funding, 0)], &[(PAYEE, 400_000), (ADDRESS, 590_000)]);
+    let mut change = chain.fund(UTXO_ADDRESS, 1_000_000);
+    for i in 0..29 {
+        let vout = if i == 0 { 0 } else { 1 };
+        change = chain.send(&[(&change, vout)], &[(PAYEE, 1_000), (UTXO_ADDRESS, 998_000 - i * 2_000)]);
+    }
+    chain.mine();
+    chain.mine();
+    chain.send(&[(&tx, 1)], &[(ADDRESS, 580_000)]);
+    let block = chain.block_hash(3).unwrap().to_string();
+    let server = MockServer::start(chain).unwrap();
+    let client = ApiClient::new(&server.url(), None).unwrap();
+    Mock {
+        server,
+        client,
+        block,
+        tx,
+    }
 }
 #[test]
 fn async_client() {
@@ -17,11 +55,23 @@ fn async_client_custom_header() {
         headers: Some(HeadersOptions {
             authorization: Some("secret".to_string()),
         }),
+        ..Default::default()
     };
     let client = ApiClient::new(ENDPOINT_URL, Some(options));
     assert!(client.is_ok());
 }
 #[test]
+fn async_client_invalid_authorization_header() {
+    let options = ClientOptions {
+        headers: Some(HeadersOptions {
+            authorization: Some("secret\n".to_string()),
+        }),
+        ..Default::default()
+    };
+    let client = ApiClient::new(ENDPOINT_URL, Some(options));
+    assert!(matches!(client, Err(Error::InvalidInput(_))));
+}
+#[test]
 fn async_client_custom_reqwest_builder() {
     let mut headers = header::HeaderMap::new();
     headers.insert(
@@ -38,297 +88,383 @@ fn async_client_custom_reqwest_builder() {
 
 #[tokio::test]
 async fn async_get_block() {
-    let client = default_client();
-    let response = client
-        .get_block("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")
-        .await;
-    assert!(response.is_ok());
+    let mock = mock();
+    let block = mock.client.get_block(&mock.block).await.unwrap();
+    assert_eq!(block.height, 3);
+    assert_eq!(block.tx_count, 30);
 }
 #[tokio::test]
 async fn async_get_block_status() {
-    let client = default_client();
-    let response = client
-        .get_block_status("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")
-        .await;
-    assert!(response.is_ok());
+    let mock = mock();
+    let status = mock.client.get_block_status(&mock.block).await.unwrap();
+    assert!(status.in_best_chain);
+    assert_eq!(status.next_best.as_deref(), mock.server.chain().block_hash(4));
 }
 #[tokio::test]
 async fn async_get_block_txs_with_and_without_index() {
-    let client = default_client();
-    let first_txs_index = client
-        .get_block_txs(
-            "000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7",
-            None,
-        )
-        .await;
-    let second_txs_index = client
-        .get_block_txs(
-            "000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7",
-            Some(25),
-        )
-        .await;
-    assert!(first_txs_index.is_ok());
-    assert!(second_txs_index.is_ok());
+    let mock = mock();
+    let first_txs_index = mock.client.get_block_txs(&mock.block, None).await.unwrap();
+    let second_txs_index = mock.client.get_block_txs(&mock.block, Some(25)).await.unwrap();
+    assert_eq!(first_txs_index.len(), 25);
+    assert_eq!(second_txs_index.len(), 5);
 }
 #[tokio::test]
 async fn async_get_block_txids() {
-    let client = default_client();
-    let txids_list = client
-        .get_block_txids("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")
-        .await;
-    assert!(txids_list.is_ok());
+    let mock = mock();
+    let txids_list = mock.client.get_block_txids(&mock.block).await.unwrap();
+    assert_eq!(txids_list.len(), 30);
 }
 #[tokio::test]
 async fn async_get_block_txid_at_index() {
-    let client = default_client();
-    let txid = client
-        .get_block_txid_at_index(
-            "000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7",
-            2,
-        )
-        .await;
-    assert!(txid.is_ok());
+    let mock = mock();
+    let txid = mock.client.get_block_txid_at_index(&mock.block, 2).await.unwrap();
+    assert_eq!(txid, mock.server.chain().block_txids(&mock.block).unwrap()[2]);
 }
 
 #[tokio::test]
 async fn async_get_block_raw_format() {
-    let client = default_client();
-    let response = client
-        .get_block_raw_format("000000000000003aaa3b99e31ed1cac4744b423f9e52ada4971461c81d4192f7")
-        .await;
-    assert_eq!(response.is_err(), false);
+    let mock = mock();
+    let response = mock.client.get_block_raw_format(&mock.block).await;
+    assert!(response.is_ok());
 }
 #[tokio::test]
 async fn async_get_block_height() {
-    let client = default_client();
-    let block_hash = client.get_block_height(424242).await.unwrap();
-    let block = client.get_block(&block_hash).await;
-    assert!(block.is_ok());
+    let mock = mock();
+    let block_hash = mock.client.get_block_height(3).await.unwrap();
+    assert_eq!(block_hash, mock.block);
 }
 #[tokio::test]
 // Return 10 blocks from start_height
 async fn async_get_blocks() {
-    let client = default_client();
-    let blocks = client.get_blocks(1234).await;
-    assert!(blocks.is_ok());
+    let mock = mock();
+    let blocks = mock.client.get_blocks(3).await.unwrap();
+    let heights: Vec<u32> = blocks.iter().map(|block| block.height).collect();
+    assert_eq!(heights, vec![3, 2, 1, 0]);
 }
 #[tokio::test]
 // Function need return last block height
 async fn async_get_blocks_tip_height() {
-    let client = default_client();
-    let height = client.get_blocks_tip_height().await;
+    let mock = mock();
+    let height = mock.client.get_blocks_tip_height().await;
 
-    assert!(height.is_ok());
+    assert_eq!(height.unwrap(), 4);
 }
 #[tokio::test]
 // Verify function return hash
 async fn async_get_blocks_tip_hash() {
-    let client = default_client();
-    let hash = client.get_blocks_tip_hash().await;
+    let mock = mock();
+    let hash = mock.client.get_blocks_tip_hash().await;
 
-    assert!(hash.is_ok());
+    assert_eq!(hash.unwrap(), mock.server.chain().tip_hash());
 }
 #[tokio::test]
-// Check tx version
+// Check tx outputs
 async fn async_get_tx() {
-    let client = default_client();
-    let tx = client
-        .get_tx("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")
-        .await;
-    assert_eq!(tx.is_err(), false);
+    let mock = mock();
+    let tx = mock.client.get_tx(&mock.tx).await.unwrap();
+    assert_eq!(tx.vout[0].scriptpubkey_address.as_deref(), Some(PAYEE));
+    assert_eq!(tx.fee, 10_000);
 }
 #[tokio::test]
 // Tx status is confirmed
 async fn async_get_tx_status() {
-    let client = default_client();
-    let tx_status = client
-        .get_tx_status("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")
-        .await;
-    assert!(tx_status.is_ok());
+    let mock = mock();
+    let tx_status = mock.client.get_tx_status(&mock.tx).await.unwrap();
+    assert!(tx_status.confirmed);
+    assert_eq!(tx_status.block_height, Some(2));
 }
 #[tokio::test]
 // Tx raw
 async fn async_get_tx_raw() {
-    let client = default_client();
-    let tx_raw = client
-        .get_tx_raw("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")
-        .await;
-    assert!(tx_raw.is_ok());
+    let mock = mock();
+    mock.server.chain().set_raw_tx(&mock.tx, vec![1, 2, 3]);
+    let tx_raw = mock.client.get_tx_raw(&mock.tx).await;
+    assert_eq!(tx_raw.unwrap(), vec![1, 2, 3]);
+}
+#[cfg(feature = "bitcoin")]
+#[tokio::test]
+async fn async_get_tx_decoded() {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+
+    let mock = mock();
+    let transaction = bitcoin::Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![bitcoin::TxIn::default()],
+        output: vec![bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(400_000),
+            script_pubkey: bitcoin::ScriptBuf::new(),
+        }],
+    };
+    mock.server
+        .chain()
+        .set_raw_tx(&mock.tx, bitcoin::consensus::encode::serialize(&transaction));
+    let tx = mock.client.get_tx_decoded(&mock.tx).await;
+    assert_eq!(tx.unwrap().compute_txid(), transaction.compute_txid());
 }
 #[tokio::test]
 // Tx hex
 async fn async_get_tx_hex() {
-    let client = default_client();
-    let tx_hex = client
-        .get_tx_hex("c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24")
-        .await;
-    assert!(tx_hex.is_ok());
+    let mock = mock();
+    mock.server.chain().set_raw_tx(&mock.tx, vec![0xab, 0xcd]);
+    let tx_hex = mock.client.get_tx_hex(&mock.tx).await;
+    assert_eq!(tx_hex.unwrap(), "abcd");
 }
 #[tokio::test]
 async fn async_get_tx_merkleblock_proof() {
-    let client = default_client();
-    let tx_hex = client
-        .get_tx_merkleblock_proof(
-            "c9ee6eff3d73d6cb92382125c3207f6447922b545d4d4e74c47bfeb56fff7d24",
-        )
-        .await;
+    let mock = mock();
+    let tx_hex = mock.client.get_tx_merkleblock_proof(&mock.tx).await;
     assert!(tx_hex.is_ok());
 }
 #[tokio::test]
 async fn async_get_tx_merkle_proof() {
-    let client = default_client();
-    let merkle_proof = client
-        .get_tx_merkle_proof("6814c0b3915a8de663851b9887e0cce7d0d6c6b3f7c28b97ba8a643b72e1b7c3")
-        .await;
-    assert!(merkle_proof.is_ok());
+    let mock = mock();
+    let merkle_proof = mock.client.get_tx_merkle_proof(&mock.tx).await.unwrap();
+    assert_eq!(merkle_proof.block_height, 2);
+    assert_eq!(merkle_proof.pos, 1);
 }
 #[tokio::test]
 async fn async_get_tx_outspend() {
-    let client = default_client();
-    let outspend = client
-        .get_tx_outspend(
-            "fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316",
-            Some(1),
-        )
-        .await;
-    assert!(outspend.is_ok());
+    let mock = mock();
+    let outspend = mock.client.get_tx_outspend(&mock.tx, Some(1)).await.unwrap();
+    assert!(outspend.spent);
+    assert_eq!(outspend.status.map(|status| status.confirmed), Some(false));
 }
 #[tokio::test]
 async fn async_get_tx_outspends() {
-    let client = default_client();
-    let outpends = client
-        .get_tx_outspends("fac9af7f793330af3cc0bce4790d98499c59d47a125af7260edd61d647003316")
-        .await;
-    assert!(outpends.is_ok());
+    let mock = mock();
+    let outpends = mock.client.get_tx_outspends(&mock.tx).await.unwrap();
+    let spent: Vec<bool> = outpends.iter().map(|outspend| outspend.spent).collect();
+    assert_eq!(spent, vec![false, true]);
 }
 #[tokio::test]
 async fn async_post_tx() {
-    let client = default_client();
-    let resp =  client.post_tx("010000000001010000000000000000000000000000000000000000000000000000000000000000ffffffff2003220d1c04d6d37c5f0877fffb9a4b3500000d2f6e6f64655374726174756d2f00000000030000000000000000266a24aa21a9ed61dc942663feda48033d1026d2fa8acf0f098870202c541bffa7771e8dc51e159b0e2801000000001976a914dfdf4d53296fac595dc33d8ac7216ba516b8dcc588ac8ffd0200000000001976a914bfcc245931cbad63d09f62df43bcab989991014e88ac0120000000000000000000000000000000000000000000000000000000000000000000000000").await;
-    assert!(resp.is_ok());
+    let mock = mock();
+    let resp = mock.client.post_tx(COINBASE_HEX).await;
+    assert!(matches!(resp, Err(Error::BroadcastRejected { .. })));
+    mock.server.chain().accept_broadcast(COINBASE_HEX, &mock.tx);
+    assert_eq!(mock.client.post_tx(COINBASE_HEX).await.unwrap(), mock.tx);
+    assert_eq!(mock.server.chain().broadcasts().len(), 2);
+}
+#[tokio::test]
+async fn async_get_block_height_not_found() {
+    let mock = mock();
+    let response = mock.client.get_block_height(999999999).await;
+    assert!(matches!(response, Err(Error::HttpStatus { status: 404, .. })));
 }
 #[tokio::test]
 async fn async_get_address() {
-    let client = default_client();
-    let address = client
-        .get_address("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")
-        .await;
-    assert!(address.is_ok())
+    let mock = mock();
+    let address = mock.client.get_address(ADDRESS).await.unwrap();
+    assert_eq!(address.chain_stats.funded_txo_count, 2);
+    assert_eq!(address.chain_stats.spent_txo_count, 1);
+    assert_eq!(address.mempool_stats.tx_count, 1);
 }
 #[tokio::test]
 async fn async_get_script_hash() {
-    let client = default_client();
-    let address = client
-        .get_script_hash("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")
-        .await;
-    assert!(address.is_ok())
+    let mock = mock();
+    let address = mock
+        .client
+        .get_script_hash(&MockChain::script_hash(ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(address.chain_stats.tx_count, 2);
 }
 #[tokio::test]
 async fn async_get_address_txs() {
-    let client = default_client();
-    let tx_list = client
-        .get_address_txs("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")
-        .await;
-    assert!(tx_list.is_ok())
+    let mock = mock();
+    let tx_list = mock.client.get_address_txs(ADDRESS).await.unwrap();
+    let confirmed: Vec<bool> = tx_list
+        .iter()
+        .map(|tx| tx.status.as_ref().is_some_and(|status| status.confirmed))
+        .collect();
+    assert_eq!(confirmed, vec![false, true, true]);
 }
 #[tokio::test]
 async fn async_get_script_hash_txs() {
-    let client = default_client();
-    let tx_list = client
-        .get_script_hash_txs("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")
-        .await;
-    assert!(tx_list.is_ok())
+    let mock = mock();
+    let tx_list = mock
+        .client
+        .get_script_hash_txs(&MockChain::script_hash(ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(tx_list.len(), 3);
 }
 #[tokio::test]
 async fn async_get_address_txs_chain() {
-    let client = default_client();
-    let tx_list = client
-        .get_address_txs_chain(
-            "n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk",
-            Some("d0075b62f8b3e464472b8edecf56083ca3e9e8424f5f332ed2f9045d7fcccddc"),
-        )
-        .await;
-    let tx_list_from_index = client
-        .get_address_txs_chain(
-            "n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk",
-            Some(&tx_list.unwrap()[1].txid),
-        )
-        .await;
-    assert!(tx_list_from_index.is_ok())
+    let mock = mock();
+    let tx_list = mock.client.get_address_txs_chain(PAYEE, None).await.unwrap();
+    let tx_list_from_index = mock
+        .client
+        .get_address_txs_chain(PAYEE, Some(&tx_list[1].txid))
+        .await
+        .unwrap();
+    assert_eq!(tx_list.len(), 25);
+    assert_eq!(tx_list_from_index[0].txid, tx_list[2].txid);
+    assert_eq!(tx_list_from_index.len(), 25);
 }
 #[tokio::test]
 async fn async_get_script_hash_txs_chain() {
-    let client = default_client();
-    let tx_list = client
-        .get_script_hash_txs_chain(
-            "c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c",
-            None,
-        )
+    let mock = mock();
+    let tx_list = mock
+        .client
+        .get_script_hash_txs_chain(&MockChain::script_hash(PAYEE), None)
         .await;
-    assert!(tx_list.is_ok())
+    assert_eq!(tx_list.unwrap().len(), 25);
 }
 #[tokio::test]
 async fn async_get_address_txs_mempool() {
-    let client = default_client();
-    let tx_list = client
-        .get_address_txs_mempool("2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5")
-        .await;
-    assert!(tx_list.is_ok())
+    let mock = mock();
+    let tx_list = mock.client.get_address_txs_mempool(ADDRESS).await;
+    assert_eq!(tx_list.unwrap().len(), 1);
 }
 #[tokio::test]
 async fn async_get_script_hash_txs_mempool() {
-    let client = default_client();
-    let tx_list = client
-        .get_script_hash_txs_mempool(
-            "c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c",
-        )
+    let mock = mock();
+    let tx_list = mock
+        .client
+        .get_script_hash_txs_mempool(&MockChain::script_hash(PAYEE))
         .await;
-    assert!(tx_list.is_ok())
+    assert!(tx_list.unwrap().is_empty());
 }
 #[tokio::test]
 async fn async_get_address_utxo() {
-    let client = default_client();
-    let utxo = client
-        .get_address_utxo("2NDcM3CGUTwqFL7y8BSBJTYJ9kToeXawkUF")
-        .await;
-    assert!(utxo.is_ok())
+    let mock = mock();
+    let utxo = mock.client.get_address_utxo(UTXO_ADDRESS).await.unwrap();
+    assert_eq!(utxo.len(), 1);
+    assert_eq!(utxo[0].value, Some(942_000));
 }
 #[tokio::test]
 async fn async_get_script_hash_utxo() {
-    let client = default_client();
-    let utxo = client
-        .get_script_hash_utxo("c6598a8e5728c744b9734facbf1e786c3ff5101268739d38b14ea475b60eba3c")
+    let mock = mock();
+    let utxo = mock
+        .client
+        .get_script_hash_utxo(&MockChain::script_hash(PAYEE))
         .await;
-    assert!(utxo.is_ok())
+    assert_eq!(utxo.unwrap().len(), 30);
 }
 #[tokio::test]
 async fn async_get_address_prefix() {
-    let client = default_client();
-    let addresses = client.get_address_prefix("2NDcM").await;
-    assert!(addresses.is_ok())
+    let mock = mock();
+    let addresses = mock.client.get_address_prefix("2NDcM").await;
+    assert_eq!(addresses.unwrap(), vec![UTXO_ADDRESS.to_string()]);
 }
 
 #[tokio::test]
 async fn async_get_mempool() {
-    let client = default_client();
-    let mempool = client.get_mempool().await;
-    assert!(mempool.is_ok())
+    let mock = mock();
+    let mempool = mock.client.get_mempool().await.unwrap();
+    assert_eq!(mempool.count, 1);
+    assert_eq!(mempool.total_fee, 10_000);
 }
 #[tokio::test]
 async fn async_get_mempool_txids() {
-    let client = default_client();
-    let mempool_txids = client.get_mempool_txids().await;
-    assert!(mempool_txids.is_ok())
+    let mock = mock();
+    let mempool_txids = mock.client.get_mempool_txids().await;
+    assert_eq!(mempool_txids.unwrap(), mock.server.chain().mempool_txids());
 }
 #[tokio::test]
 async fn async_get_mempool_recent() {
-    let client = default_client();
-    let mempool_txids = client.get_mempool_recent().await;
-    assert!(mempool_txids.is_ok())
+    let mock = mock();
+    let mempool_txids = mock.client.get_mempool_recent().await.unwrap();
+    assert_eq!(mempool_txids[0].value, 580_000);
 }
 #[tokio::test]
 async fn async_fee_estimate() {
-    let client = default_client();
-    let fee = client.fee_estimate().await;
-    assert!(fee.is_ok())
+    let mock = mock();
+    mock.server.chain().set_fee_estimates(&[(1, 42.0)]);
+    let fee = mock.client.fee_estimate().await.unwrap();
+    assert_eq!(fee.get("1"), Some(&42.0));
+}
+#[tokio::test]
+async fn async_mock_retries_throttled_requests() {
+    let mock = mock();
+    mock.server.throttle_next(2, Duration::from_secs(0));
+    let retry = RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let client = mock.client.with_retry(retry);
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 4);
+    assert_eq!(mock.server.requests().len(), 3);
+}
+#[tokio::test]
+async fn async_mock_injected_failures() {
+    let mock = mock();
+    mock.server.fail_path("/tx/", 503);
+    let response = mock.client.get_tx(&mock.tx).await;
+    assert!(matches!(response, Err(Error::HttpStatus { status: 503, .. })));
+    assert!(mock.client.get_block(&mock.block).await.is_ok());
+    mock.server.clear_faults();
+    mock.server.set_latency(Duration::from_millis(500));
+    let client = mock.client.with_timeout(Duration::from_millis(50));
+    assert!(client.get_tx(&mock.tx).await.is_err());
+}
+#[tokio::test]
+async fn async_mock_reorg() {
+    let mock = mock();
+    let tip = mock.server.chain().disconnect_tip();
+    let status = mock.client.get_block_status(&tip).await.unwrap();
+    assert!(!status.in_best_chain);
+    assert_eq!(mock.client.get_blocks_tip_height().await.unwrap(), 3);
+}
+#[tokio::test]
+async fn async_record_and_replay() {
+    let fixture = std::env::temp_dir().join(format!("esplora-api-async-vcr-{}.json", std::process::id()));
+    let mock = mock();
+    let recorder = Recorder::new(reqwest::Client::new(), &fixture);
+    let client = ApiClient::with_transport(&mock.server.url(), recorder).unwrap();
+    let tx = client.get_tx(&mock.tx).await.unwrap();
+    let raw = client.get_block_raw_format(&mock.block).await.unwrap();
+    let tip = client.get_blocks_tip_height().await.unwrap();
+    mock.server.chain().mine();
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), tip + 1);
+    let block = mock.block.clone();
+    drop(mock);
+
+    let client = ApiClient::with_transport("https://esplora.test/", Replay::load(&fixture).unwrap()).unwrap();
+    assert_eq!(client.get_tx(&tx.txid).await.unwrap(), tx);
+    assert_eq!(client.get_block_raw_format(&block).await.unwrap(), raw);
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), tip);
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), tip + 1);
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), tip + 1);
+    std::fs::remove_file(&fixture).unwrap();
+}
+#[tokio::test]
+async fn async_replay_unrecorded_request() {
+    let fixture = std::env::temp_dir().join(format!("esplora-api-async-unrecorded-{}.json", std::process::id()));
+    std::fs::write(&fixture, "[]").unwrap();
+    let client = ApiClient::with_transport("https://esplora.test", Replay::load(&fixture).unwrap()).unwrap();
+    match client.get_blocks_tip_hash().await {
+        Err(Error::InvalidInput(message)) => assert!(message.starts_with("no response recorded for GET /blocks/tip/hash in ")),
+        other => panic!("unexpected {:?}", other),
+    }
+    std::fs::remove_file(&fixture).unwrap();
+}
+#[cfg(feature = "liquid")]
+#[tokio::test]
+async fn async_get_asset() {
+    let mock = mock();
+    let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+    mock.server.set_response(
+        &format!("/asset/{}", asset_id),
+        200,
+        include_str!("fixtures/liquid_asset.json"),
+    );
+    mock.server.set_response(&format!("/asset/{}/supply", asset_id), 200, "2100000000000000");
+    let asset = mock.client.get_asset(asset_id).await;
+    let supply = mock.client.get_asset_supply(asset_id).await;
+    assert!(asset.is_ok());
+    assert!(supply.is_ok());
+}
+#[cfg(feature = "liquid")]
+#[tokio::test]
+async fn async_get_assets_registry_encodes_query() {
+    let mock = mock();
+    let path = "/assets/registry?limit=5&sort_field=name%26limit%3D1000&sort_dir=asc";
+    mock.server.set_response(path, 200, "[]");
+    let assets = mock.client.get_assets_registry(None, Some(5), Some("name&limit=1000"), Some("asc")).await;
+    assert!(assets.unwrap().is_empty());
+    assert!(mock.server.requests().iter().any(|request| request.ends_with(path)));
 }
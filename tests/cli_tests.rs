@@ -0,0 +1,78 @@
+use std::process::{Command, Output};
+
+use esplora_api::testing::{MockChain, MockServer};
+
+static ADDRESS: &str = "2MvJVm11phGoxEekPB8Hw2Tksb57eVRGHC5";
+static PAYEE: &str = "n1vgV8XmoggmRXzW3hGD8ZNTAgvhcwT4Gk";
+
+/// Mock Esplora server where ADDRESS pays PAYEE at height 2, and the txid of the payment.
+fn mock() -> (MockServer, String) {
+    let mut chain = MockChain::new();
+    let funding = chain.fund(ADDRESS, 1_000_000);
+    let tx = chain.send(&[(&funding, 0)], &[(PAYEE, 400_000), (ADDRESS, 590_000)]);
+    chain.mine();
+    (MockServer::start(chain).unwrap(), tx)
+}
+
+/// Run the esplora binary against server.
+fn esplora(server: &MockServer, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_esplora"))
+        .arg("--url")
+        .arg(server.url())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn cli_tx_json() {
+    let (server, tx) = mock();
+    let output = stdout(&esplora(&server, &["tx", &tx]));
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["txid"], tx.as_str());
+    assert_eq!(json["vout"][0]["value"], 400_000);
+}
+#[test]
+fn cli_tip_raw() {
+    let (server, _) = mock();
+    let output = stdout(&esplora(&server, &["tip", "-o", "raw"]));
+    assert_eq!(output, "2\n");
+    let hash = stdout(&esplora(&server, &["tip", "--hash", "-o", "raw"]));
+    assert_eq!(hash.trim(), server.chain().tip_hash());
+}
+#[test]
+fn cli_address_utxo_table() {
+    let (server, tx) = mock();
+    let output = stdout(&esplora(&server, &["address", "utxo", PAYEE, "--output", "table"]));
+    let lines: Vec<Vec<&str>> = output.lines().map(|line| line.split_whitespace().collect()).collect();
+    assert_eq!(lines.len(), 2);
+    let column = |name: &str| lines[0].iter().position(|header| *header == name).unwrap();
+    assert_eq!(lines[1][column("txid")], tx);
+    assert_eq!(lines[1][column("value")], "400000");
+    assert_eq!(lines[1][column("status.confirmed")], "true");
+}
+#[test]
+fn cli_broadcast_file() {
+    let (server, _) = mock();
+    let hex = "0100000001";
+    let txid = "a".repeat(64);
+    server.chain().accept_broadcast(hex, &txid);
+    let path = std::env::temp_dir().join(format!("esplora-cli-broadcast-{}.hex", std::process::id()));
+    std::fs::write(&path, format!("{}\n", hex)).unwrap();
+    let output = esplora(&server, &["broadcast", path.to_str().unwrap(), "-o", "raw"]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(stdout(&output).trim(), txid);
+    assert_eq!(server.chain().broadcasts(), vec![hex.to_string()]);
+}
+#[test]
+fn cli_error_exit_status() {
+    let (server, _) = mock();
+    let output = esplora(&server, &["tx", &"0".repeat(64)]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error:"));
+}
@@ -0,0 +1,163 @@
+use esplora_api::data::blockstream::{
+    AddressInfoFormat, BlockFormat, BlockStatus, MemPoolFormat, MempoolTxFormat, MerkleProofFormat,
+    OutspentFormat, TransactionFormat, UtxoFormat,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Decode a fixture and check that serializing it gives back the exact Esplora JSON.
+fn fixture<T: DeserializeOwned + Serialize>(json: &str) -> T {
+    let data: T = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&data).unwrap(), json.trim());
+    data
+}
+#[test]
+fn data_block() {
+    let block: BlockFormat = fixture(include_str!("fixtures/block.json"));
+    assert_eq!(block.height, 100000);
+    assert_eq!(block.bits, Some(453281356));
+    assert!((block.difficulty.unwrap() - 14484.1623612254).abs() < 1e-9);
+    assert_eq!(block.mediantime, 1293622620);
+}
+#[test]
+fn data_block_genesis() {
+    let block: BlockFormat = fixture(include_str!("fixtures/block_genesis.json"));
+    assert_eq!(block.height, 0);
+    assert!(block.previousblockhash.is_none());
+    assert_eq!(block.nonce, Some(2083236893));
+}
+#[test]
+fn data_block_status() {
+    let status: BlockStatus = fixture(include_str!("fixtures/block_status.json"));
+    assert!(status.in_best_chain);
+    assert_eq!(status.height, Some(100000));
+    assert!(status.next_best.is_some());
+}
+#[test]
+fn data_block_status_tip() {
+    let status: BlockStatus = fixture(include_str!("fixtures/block_status_tip.json"));
+    assert!(status.next_best.is_none());
+}
+#[test]
+fn data_transaction_large_outputs() {
+    let tx: TransactionFormat = fixture(include_str!("fixtures/tx.json"));
+    let prevout = tx.vin[0].prevout.as_ref().unwrap();
+    assert_eq!(prevout.value, Some(5_000_000_000));
+    assert_eq!(tx.vout[1].value, Some(4_000_000_000));
+    assert_eq!(tx.vout[0].scriptpubkey_address, None);
+    assert_eq!(tx.status.unwrap().block_height, Some(170));
+    assert!(tx.vin[0].witness.is_none());
+}
+#[test]
+fn data_transaction_segwit_unconfirmed() {
+    let tx: TransactionFormat = fixture(include_str!("fixtures/tx_segwit_unconfirmed.json"));
+    let vin = &tx.vin[0];
+    assert_eq!(vin.witness.as_ref().unwrap().len(), 4);
+    assert!(vin.inner_redeemscript_asm.as_ref().unwrap().starts_with("OP_0 OP_PUSHBYTES_32"));
+    assert!(vin.inner_witnessscript_asm.as_ref().unwrap().ends_with("OP_CHECKMULTISIG"));
+    assert_eq!(tx.vout[1].scriptpubkey_type, "v1_p2tr");
+    let status = tx.status.unwrap();
+    assert!(!status.confirmed);
+    assert!(status.block_time.is_none());
+}
+#[test]
+fn data_transaction_coinbase() {
+    let tx: TransactionFormat = fixture(include_str!("fixtures/tx_coinbase.json"));
+    assert!(tx.vin[0].is_coinbase);
+    assert!(tx.vin[0].prevout.is_none());
+    assert_eq!(tx.vin[0].vout, u32::MAX);
+    assert_eq!(tx.vout[0].value, Some(5_000_000_000));
+}
+#[test]
+fn data_utxo() {
+    let utxos: Vec<UtxoFormat> = fixture(include_str!("fixtures/utxo.json"));
+    assert_eq!(utxos.len(), 2);
+    assert_eq!(utxos[0].value, Some(5_000_000_000));
+    assert_eq!(utxos[1].vout, 1);
+}
+#[test]
+fn data_address() {
+    let address: AddressInfoFormat = fixture(include_str!("fixtures/address.json"));
+    assert_eq!(address.chain_stats.funded_txo_sum, 10_145_932_281);
+    assert!(address.scripthash.is_none());
+}
+#[test]
+fn data_mempool() {
+    let mempool: MemPoolFormat = fixture(include_str!("fixtures/mempool.json"));
+    assert!(mempool.count > 0 && mempool.total_fee > 0);
+    assert!(!mempool.fee_histogram.is_empty());
+    // fee rates in descending order
+    assert!(mempool.fee_histogram.windows(2).all(|bins| bins[0].0 >= bins[1].0));
+}
+#[test]
+fn data_mempool_recent() {
+    let recent: Vec<MempoolTxFormat> = fixture(include_str!("fixtures/mempool_recent.json"));
+    assert!(!recent.is_empty() && recent.len() <= 10);
+    assert!(recent.iter().all(|tx| tx.fee > 0 && tx.vsize > 0));
+}
+#[test]
+fn data_merkle_proof() {
+    let proof: MerkleProofFormat = fixture(include_str!("fixtures/merkle_proof.json"));
+    assert_eq!(proof.pos, 1);
+    assert_eq!(proof.merkle.len(), 1);
+}
+#[test]
+fn data_outspends() {
+    let outspends: Vec<OutspentFormat> = fixture(include_str!("fixtures/outspends.json"));
+    assert!(!outspends[0].spent);
+    assert!(outspends[0].txid.is_none());
+    assert_eq!(outspends[1].vin, Some(0));
+}
+#[test]
+fn data_clone_eq() {
+    let tx: TransactionFormat = fixture(include_str!("fixtures/tx_segwit_unconfirmed.json"));
+    let mut other = tx.clone();
+    assert_eq!(tx, other);
+    other.vin[0].witness = None;
+    assert_ne!(tx, other);
+}
+#[cfg(feature = "bitcoin")]
+#[test]
+fn data_bitcoin_accessors() {
+    use esplora_api::bitcoin::Amount;
+    let tx: TransactionFormat = fixture(include_str!("fixtures/tx_segwit_unconfirmed.json"));
+    assert_eq!(tx.txid().unwrap().to_string(), tx.txid);
+    assert_eq!(tx.vin[0].witness_stack().unwrap().len(), 4);
+    assert_eq!(tx.vin[0].previous_output().unwrap().vout, 1);
+    assert!(tx.vout[1].script_pubkey().unwrap().is_p2tr());
+    assert_eq!(tx.vout[0].amount(), Some(Amount::from_btc(80.0).unwrap()));
+    assert!(tx.vout[0].address().unwrap().is_some());
+    let block: BlockFormat = fixture(include_str!("fixtures/block_genesis.json"));
+    assert_eq!(block.block_hash().unwrap().to_string(), block.id);
+    assert!(block.prev_block_hash().unwrap().is_none());
+}
+#[cfg(feature = "liquid")]
+#[test]
+fn data_liquid_transaction() {
+    let tx: TransactionFormat = fixture(include_str!("fixtures/liquid_tx.json"));
+    assert!(tx.vin.iter().all(|vin| vin.is_pegin.is_some()));
+    let confidential = tx.vout.iter().find(|vout| vout.valuecommitment.is_some()).unwrap();
+    assert!(confidential.value.is_none());
+    assert!(confidential.asset.is_none());
+    let fee = tx.vout.iter().find(|vout| vout.scriptpubkey_type == "fee").unwrap();
+    assert_eq!(fee.value, Some(tx.fee));
+    assert!(fee.asset.is_some());
+}
+#[cfg(feature = "liquid")]
+#[test]
+fn data_liquid_block() {
+    let block: BlockFormat = fixture(include_str!("fixtures/liquid_block.json"));
+    assert!(block.difficulty.is_none() && block.bits.is_none() && block.nonce.is_none());
+    assert!(block.proof.unwrap().challenge.ends_with("ae"));
+}
+#[cfg(feature = "liquid")]
+#[test]
+fn data_liquid_asset() {
+    use esplora_api::data::liquid::{AssetFormat, AssetStats};
+    let asset: AssetFormat = fixture(include_str!("fixtures/liquid_asset.json"));
+    match asset.chain_stats {
+        AssetStats::Native(stats) => assert_eq!(stats.peg_in_count, 1620),
+        AssetStats::Issued(_) => panic!("native asset decoded as issued asset"),
+    }
+    assert!(asset.name.is_none());
+}
@@ -0,0 +1,46 @@
+#!/bin/sh
+# Capture the live Esplora responses used as fixtures from blockstream.info.
+# Needs curl and jq. Run from anywhere, then record the printed sources in SOURCES.md.
+#
+# Usage: tests/fixtures/capture.sh [liquid_height]
+set -eu
+cd "$(dirname "$0")"
+
+api=https://blockstream.info/api
+liquid=https://blockstream.info/liquid/api
+height=${1:-3000000}
+
+# Write the body of GET $1 to $2 byte for byte, as the tests check the round trip against it.
+fetch() {
+    curl -sSf "$1" > "$2.tmp" && mv "$2.tmp" "$2"
+    echo "$2 <- GET $1"
+}
+
+tip=$(curl -sSf "$api/blocks/tip/height")
+fetch "$api/mempool" mempool.json
+fetch "$api/mempool/recent" mempool_recent.json
+fetch "$api/block/$(curl -sSf "$api/block-height/$tip")/status" block_status_tip.json
+echo "  bitcoin tip height $tip, $(date -u +%Y-%m-%d)"
+fetch "$api/address/1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa" address.json
+fetch "$api/tx/f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16/outspends" outspends.json
+
+# first recent mempool transaction spending a witness input, and the unspent outputs of its first address
+for txid in $(curl -sSf "$api/mempool/recent" | jq -r '.[].txid'); do
+    [ "$(curl -sSf "$api/tx/$txid" | jq '.vin[0].witness != null')" = true ] && break
+done
+fetch "$api/tx/$txid" tx_segwit_unconfirmed.json
+address=$(jq -r '[.vout[].scriptpubkey_address | select(. != null)][0]' tx_segwit_unconfirmed.json)
+fetch "$api/address/$address/utxo" utxo.json
+
+# first Liquid block from $height holding a transaction besides its coinbase
+while :; do
+    hash=$(curl -sSf "$liquid/block-height/$height")
+    count=$(curl -sSf "$liquid/block/$hash" | jq .tx_count)
+    [ "$count" -gt 1 ] && break
+    height=$((height + 1))
+done
+fetch "$liquid/block/$hash" liquid_block.json
+echo "  liquid height $height"
+txid=$(curl -sSf "$liquid/block/$hash/txid/1")
+fetch "$liquid/tx/$txid" liquid_tx.json
+fetch "$liquid/asset/6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d" liquid_asset.json
@@ -0,0 +1,1045 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use esplora_api::async_impl::{ApiClient, EsploraTransport, QuorumClient, TransportFuture};
+use esplora_api::data::blockstream::TxStatusFormat;
+use esplora_api::transport::{Method, Request, Response};
+use esplora_api::{
+    CachePolicy, CacheStats, ClientOptions, Error, Failover, FsStore, Network, RateLimit, RetryPolicy,
+};
+
+static ENDPOINT_URL: &str = "https://esplora.test/api";
+/// Block confirming the transaction of fixtures/tx.json.
+static TX_BLOCK: &str = "00000000d1145790a8694403d4063f323d499e655c83426834d4ce2f8dd4a2ee";
+
+/// Transport answering requests with responses in order, repeating the last one, and recording the requests it received.
+#[derive(Clone)]
+struct StubTransport {
+    responses: Arc<Mutex<Vec<Response>>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StubTransport {
+    fn new(status: u16, body: &str) -> Self {
+        StubTransport::sequence(vec![Response::new(status, body)])
+    }
+    fn sequence(responses: Vec<Response>) -> Self {
+        StubTransport {
+            responses: Arc::new(Mutex::new(responses)),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+    fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+    fn respond(&self, request: &Request) -> Response {
+        self.requests.lock().unwrap().push(request.clone());
+        let mut responses = self.responses.lock().unwrap();
+        if responses.len() > 1 {
+            responses.remove(0)
+        } else {
+            responses[0].clone()
+        }
+    }
+}
+
+impl EsploraTransport for StubTransport {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        let response = self.respond(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl esplora_api::blocking::EsploraTransport for StubTransport {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        Ok(self.respond(request))
+    }
+}
+
+/// Transport answering `42` except for urls starting with one of the down prefixes, answered with 503.
+#[derive(Clone, Default)]
+struct RoutedTransport {
+    down: Arc<Mutex<Vec<String>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl RoutedTransport {
+    fn set_down(&self, prefixes: &[&str]) {
+        *self.down.lock().unwrap() = prefixes.iter().map(|prefix| prefix.to_string()).collect();
+    }
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl EsploraTransport for RoutedTransport {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        self.requests.lock().unwrap().push(request.url.clone());
+        let down = self.down.lock().unwrap().iter().any(|prefix| request.url.starts_with(prefix.as_str()));
+        let response = if down {
+            Response::new(503, "Service Unavailable")
+        } else {
+            Response::new(200, "42")
+        };
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+fn failover_client(transport: &RoutedTransport, probe_interval: Duration) -> ApiClient {
+    ApiClient::with_transport("https://primary.test/api", transport.clone())
+        .unwrap()
+        .with_failover(Failover {
+            fallback_urls: vec!["https://secondary.test/api".to_string()],
+            probe_interval,
+        })
+        .unwrap()
+}
+
+fn fast_retry() -> RetryPolicy {
+    RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(10),
+        ..Default::default()
+    }
+}
+
+fn stub_client(status: u16, body: &str) -> (ApiClient, StubTransport) {
+    let transport = StubTransport::new(status, body);
+    (ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap(), transport)
+}
+#[tokio::test]
+async fn transport_get_block() {
+    let (client, transport) = stub_client(200, include_str!("fixtures/block.json"));
+    let block = client.get_block("blockhash").await.unwrap();
+    assert_eq!(block.height, 100000);
+    assert_eq!(
+        transport.requests(),
+        vec![Request {
+            method: Method::Get,
+            url: "https://esplora.test/api/block/blockhash".to_string(),
+            body: None,
+            timeout: None,
+        }]
+    );
+}
+#[tokio::test]
+async fn transport_get_tx_hex_route() {
+    let (client, transport) = stub_client(200, "0100");
+    assert_eq!(client.get_tx_hex("txid").await.unwrap(), "0100");
+    assert_eq!(transport.requests()[0].url, "https://esplora.test/api/tx/txid/hex");
+}
+#[tokio::test]
+async fn transport_get_address_txs_chain_route() {
+    let (client, transport) = stub_client(200, "[]");
+    client.get_address_txs_chain("addr", Some("txid")).await.unwrap();
+    client.get_address_txs_chain("addr", None).await.unwrap();
+    let urls: Vec<String> = transport.requests().into_iter().map(|request| request.url).collect();
+    assert_eq!(
+        urls,
+        vec![
+            "https://esplora.test/api/address/addr/txs/chain/txid",
+            "https://esplora.test/api/address/addr/txs/chain",
+        ]
+    );
+}
+#[tokio::test]
+async fn transport_http_status() {
+    let (client, _) = stub_client(404, "Block not found");
+    let res = client.get_block_height(999999999).await;
+    assert!(matches!(res, Err(Error::HttpStatus { status: 404, ref body }) if body == "Block not found"));
+}
+#[tokio::test]
+async fn transport_decode_error() {
+    let (client, _) = stub_client(200, "not json");
+    let res = client.get_mempool().await;
+    assert!(matches!(res, Err(Error::Decode { ref url, .. }) if url == "https://esplora.test/api/mempool"));
+}
+#[tokio::test]
+async fn transport_post_tx() {
+    let (client, transport) = stub_client(
+        400,
+        r#"sendrawtransaction RPC error: {"code":-26,"message":"bad-txns-inputs-missingorspent"}"#,
+    );
+    let res = client.post_tx("0200").await;
+    assert!(matches!(res, Err(Error::BroadcastRejected { code: -26, .. })));
+    let request = &transport.requests()[0];
+    assert_eq!(request.method, Method::Post);
+    assert_eq!(request.body.as_deref(), Some("0200"));
+}
+#[tokio::test]
+async fn transport_shared_by_clones() {
+    let (client, transport) = stub_client(200, "42");
+    let clone = client.clone();
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(clone.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(transport.requests().len(), 2);
+}
+#[cfg(feature = "blocking")]
+#[test]
+fn transport_blocking_get_tx() {
+    let transport = StubTransport::new(200, include_str!("fixtures/tx.json"));
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let tx = client.get_tx("txid").unwrap();
+    assert_eq!(tx.vin.len(), 1);
+    assert_eq!(transport.requests()[0].url, "https://esplora.test/api/tx/txid");
+}
+#[tokio::test]
+async fn transport_retry_transient_status() {
+    let transport = StubTransport::sequence(vec![
+        Response::new(503, "Service Unavailable"),
+        Response::new(429, "Too Many Requests"),
+        Response::new(200, "42"),
+    ]);
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(fast_retry());
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(transport.requests().len(), 3);
+}
+#[tokio::test]
+async fn transport_retry_gives_up_after_max_attempts() {
+    let transport = StubTransport::new(503, "Service Unavailable");
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(fast_retry());
+    let res = client.get_blocks_tip_height().await;
+    assert!(matches!(res, Err(Error::HttpStatus { status: 503, .. })));
+    assert_eq!(transport.requests().len(), 3);
+}
+#[tokio::test]
+async fn transport_retry_after_header() {
+    let mut throttled = Response::new(429, "Too Many Requests");
+    throttled.headers.push(("retry-after".to_string(), "0".to_string()));
+    let transport = StubTransport::sequence(vec![throttled, Response::new(200, "42")]);
+    let retry = RetryPolicy {
+        base_delay: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(retry);
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(transport.requests().len(), 2);
+}
+#[tokio::test]
+async fn transport_retry_skips_client_errors_and_post() {
+    let transport = StubTransport::new(404, "Block not found");
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(fast_retry());
+    assert!(client.get_block_height(999999999).await.is_err());
+    assert_eq!(transport.requests().len(), 1);
+
+    let transport = StubTransport::new(503, "Service Unavailable");
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(fast_retry());
+    assert!(client.post_tx("0200").await.is_err());
+    assert_eq!(transport.requests().len(), 1);
+
+    let retry = RetryPolicy {
+        retry_post: true,
+        ..fast_retry()
+    };
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(retry);
+    assert!(client.post_tx("0200").await.is_err());
+    assert_eq!(transport.requests().len(), 4);
+}
+#[cfg(feature = "blocking")]
+#[test]
+fn transport_blocking_retry() {
+    let transport = StubTransport::sequence(vec![Response::new(502, "Bad Gateway"), Response::new(200, "42")]);
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_retry(fast_retry());
+    assert_eq!(client.get_blocks_tip_height().unwrap(), 42);
+    assert_eq!(transport.requests().len(), 2);
+}
+#[tokio::test]
+async fn transport_rate_limit_shared_by_clones() {
+    let (client, transport) = stub_client(200, "42");
+    let client = client.with_rate_limit(RateLimit::new(20.0, 2).unwrap());
+    let clone = client.clone();
+    let start = Instant::now();
+    for _ in 0..2 {
+        client.get_blocks_tip_height().await.unwrap();
+        clone.get_blocks_tip_height().await.unwrap();
+    }
+    // 2 requests exceed the burst and wait 1/20s each
+    assert!(start.elapsed() >= Duration::from_millis(90));
+    assert_eq!(transport.requests().len(), 4);
+}
+#[test]
+fn transport_rate_limit_invalid() {
+    assert!(matches!(RateLimit::new(0.0, 1), Err(Error::InvalidInput(_))));
+    assert!(matches!(RateLimit::new(1.0, 0), Err(Error::InvalidInput(_))));
+}
+#[cfg(feature = "blocking")]
+#[test]
+fn transport_blocking_rate_limit() {
+    let transport = StubTransport::new(200, "42");
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, transport).unwrap()
+        .with_rate_limit(RateLimit::new(20.0, 1).unwrap());
+    let start = Instant::now();
+    for _ in 0..3 {
+        client.get_blocks_tip_height().unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+#[tokio::test]
+async fn transport_failover_to_next_endpoint() {
+    let transport = RoutedTransport::default();
+    transport.set_down(&["https://primary.test"]);
+    let client = failover_client(&transport, Duration::from_secs(60));
+    let served = client.served(|client| async move { client.get_blocks_tip_height().await }).await.unwrap();
+    assert_eq!(served.value, 42);
+    assert_eq!(served.url.as_deref(), Some("https://secondary.test/api"));
+    // dead primary is skipped until the probe interval elapsed
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(
+        transport.requests(),
+        vec![
+            "https://primary.test/api/blocks/tip/height",
+            "https://secondary.test/api/blocks/tip/height",
+            "https://secondary.test/api/blocks/tip/height",
+        ]
+    );
+    let health = client.endpoints();
+    assert!(!health[0].healthy);
+    assert_eq!(health[0].consecutive_failures, 1);
+    assert!(health[1].healthy);
+    assert_eq!(health[1].served, 2);
+}
+#[tokio::test]
+async fn transport_failover_probes_dead_endpoint() {
+    let transport = RoutedTransport::default();
+    transport.set_down(&["https://primary.test"]);
+    let client = failover_client(&transport, Duration::from_secs(0));
+    client.get_blocks_tip_height().await.unwrap();
+    transport.set_down(&[]);
+    let served = client.served(|client| async move { client.get_blocks_tip_hash().await }).await.unwrap();
+    assert_eq!(
+        transport.requests()[2..],
+        [
+            "https://primary.test/api/blocks/tip/height",
+            "https://primary.test/api/blocks/tip/hash",
+        ]
+    );
+    assert_eq!(served.url.as_deref(), Some("https://primary.test/api"));
+    assert!(client.endpoints().iter().all(|endpoint| endpoint.healthy));
+}
+#[tokio::test]
+async fn transport_failover_served_by_concurrent_calls() {
+    let transport = RoutedTransport::default();
+    transport.set_down(&["https://primary.test/api/blocks/tip/hash"]);
+    let client = failover_client(&transport, Duration::from_secs(60));
+    let (height, hash) = futures::join!(
+        client.served(|client| async move { client.get_blocks_tip_height().await }),
+        client.served(|client| async move { client.get_blocks_tip_hash().await }),
+    );
+    // the hash call failed over after the height call was served by the primary
+    assert_eq!(height.unwrap().url.as_deref(), Some("https://primary.test/api"));
+    assert_eq!(hash.unwrap().url.as_deref(), Some("https://secondary.test/api"));
+    let plain = ApiClient::with_transport(ENDPOINT_URL, StubTransport::new(200, "42")).unwrap();
+    let served = plain.served(|client| async move { client.get_blocks_tip_height().await }).await.unwrap();
+    assert_eq!(served.url.as_deref(), Some(ENDPOINT_URL));
+}
+#[tokio::test]
+async fn transport_failover_all_down() {
+    let transport = RoutedTransport::default();
+    transport.set_down(&["https://primary.test", "https://secondary.test"]);
+    let client = failover_client(&transport, Duration::from_secs(60));
+    let res = client.get_blocks_tip_height().await;
+    assert!(matches!(res, Err(Error::HttpStatus { status: 503, .. })));
+    // every endpoint is dead, they are still tried as last resort
+    client.get_blocks_tip_height().await.unwrap_err();
+    assert_eq!(transport.requests().len(), 4);
+}
+fn quorum_client(tips: &[(u16, &str)], required: usize) -> Result<QuorumClient, Error> {
+    let clients = tips
+        .iter()
+        .enumerate()
+        .map(|(index, (status, body))| {
+            ApiClient::with_transport(&format!("https://server{}.test/api", index), StubTransport::new(*status, body)).unwrap()
+        })
+        .collect();
+    QuorumClient::new(clients, required)
+}
+#[tokio::test]
+async fn transport_quorum_reached() {
+    let quorum = quorum_client(&[(200, "hash_b"), (200, "hash_a"), (200, "hash_a")], 2).unwrap();
+    assert_eq!(quorum.get_blocks_tip_hash().await.unwrap(), "hash_a");
+    let quorum = quorum_client(&[(200, "42"), (503, "Service Unavailable"), (200, "42")], 2).unwrap();
+    assert_eq!(quorum.get_blocks_tip_height().await.unwrap(), 42);
+}
+#[tokio::test]
+async fn transport_quorum_not_reached() {
+    let quorum = quorum_client(&[(200, "hash_a"), (200, "hash_b"), (503, "Service Unavailable")], 2).unwrap();
+    match quorum.get_blocks_tip_hash().await {
+        Err(Error::QuorumNotReached { required, votes }) => {
+            assert_eq!(required, 2);
+            assert_eq!(votes.len(), 3);
+            assert_eq!(votes[0].url, "https://server0.test/api");
+            assert_eq!(votes[0].value::<String>().as_deref(), Some("hash_a"));
+            assert_eq!(votes[1].value::<String>().as_deref(), Some("hash_b"));
+            assert!(matches!(votes[2].result, Err(Error::HttpStatus { status: 503, .. })));
+            assert_eq!(votes[2].value::<String>(), None);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    // two answers reaching the quorum are ambiguous, whatever their order
+    let quorum = quorum_client(&[(200, "hash_a"), (200, "hash_b"), (200, "hash_b"), (200, "hash_a")], 2).unwrap();
+    match quorum.get_blocks_tip_hash().await {
+        Err(Error::QuorumNotReached { required, votes }) => {
+            assert_eq!(required, 2);
+            let hashes: Vec<String> = votes.iter().map(|vote| vote.value().unwrap()).collect();
+            assert_eq!(hashes, vec!["hash_a", "hash_b", "hash_b", "hash_a"]);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    let quorum = quorum_client(&[(200, "hash_a"), (200, "hash_b")], 1).unwrap();
+    assert!(matches!(quorum.get_blocks_tip_hash().await, Err(Error::QuorumNotReached { .. })));
+    let status = r#"{"confirmed":true,"block_height":100,"block_hash":"hash_a","block_time":1600000000}"#;
+    let quorum = QuorumClient::new(
+        vec![
+            ApiClient::with_transport("https://server0.test/api", StubTransport::new(200, status)).unwrap(),
+            ApiClient::with_transport("https://server1.test/api", StubTransport::new(200, r#"{"confirmed":false}"#)).unwrap(),
+        ],
+        2,
+    )
+    .unwrap();
+    match quorum.get_tx_status("txid").await {
+        Err(Error::QuorumNotReached { votes, .. }) => {
+            let statuses: Vec<TxStatusFormat> = votes.iter().map(|vote| vote.value().unwrap()).collect();
+            assert_eq!(statuses[0].block_height, Some(100));
+            assert!(statuses[0].confirmed && !statuses[1].confirmed);
+            assert_eq!(votes[1].result.as_ref().unwrap()["confirmed"], false);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+}
+#[test]
+fn transport_quorum_invalid() {
+    assert!(matches!(quorum_client(&[(200, "42")], 2), Err(Error::InvalidInput(_))));
+    assert!(matches!(quorum_client(&[(200, "42")], 0), Err(Error::InvalidInput(_))));
+}
+#[cfg(feature = "blocking")]
+#[test]
+fn transport_blocking_quorum() {
+    let clients = ["42", "43", "42"]
+        .iter()
+        .map(|tip| esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, StubTransport::new(200, tip)).unwrap())
+        .collect();
+    let quorum = esplora_api::blocking::QuorumClient::new(clients, 2).unwrap();
+    assert_eq!(quorum.get_blocks_tip_height().unwrap(), 42);
+    let quorum = esplora_api::blocking::QuorumClient::new(quorum.clients().to_vec(), 3).unwrap();
+    assert!(matches!(quorum.get_blocks_tip_height(), Err(Error::QuorumNotReached { .. })));
+}
+#[tokio::test]
+async fn transport_per_call_timeout() {
+    let (client, transport) = stub_client(200, "42");
+    client.clone().with_timeout(Duration::from_secs(120)).get_blocks_tip_height().await.unwrap();
+    client.get_blocks_tip_height().await.unwrap();
+    let timeouts: Vec<_> = transport.requests().into_iter().map(|request| request.timeout).collect();
+    assert_eq!(timeouts, vec![Some(Duration::from_secs(120)), None]);
+}
+#[test]
+fn transport_client_options() {
+    let options = ClientOptions::new()
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .user_agent("esplora-api-tests")
+        .header("x-api-key", "key")
+        .proxy("http://127.0.0.1:3128");
+    assert!(ApiClient::new(ENDPOINT_URL, Some(options)).is_ok());
+    let options = ClientOptions::new().header("invalid header", "key");
+    assert!(matches!(ApiClient::new(ENDPOINT_URL, Some(options)), Err(Error::InvalidInput(_))));
+    let options = ClientOptions::new().root_certificate("not a certificate");
+    assert!(matches!(ApiClient::new(ENDPOINT_URL, Some(options)), Err(Error::InvalidInput(_))));
+}
+#[tokio::test]
+async fn transport_builder_normalizes_url() {
+    let transport = StubTransport::new(200, "42");
+    let client = ApiClient::builder()
+        .url("https://esplora.test/api/")
+        .transport(transport.clone())
+        .retry(fast_retry())
+        .build()
+        .unwrap();
+    assert_eq!(client.url, "https://esplora.test/api");
+    client.get_blocks_tip_height().await.unwrap();
+    assert_eq!(transport.requests()[0].url, "https://esplora.test/api/blocks/tip/height");
+
+    // fallback urls given to with_failover are normalized too
+    let transport = StubTransport::sequence(vec![Response::new(503, "Service Unavailable"), Response::new(200, "42")]);
+    let failover = Failover {
+        fallback_urls: vec!["https://fallback.test/api/".to_string()],
+        ..Default::default()
+    };
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_failover(failover).unwrap();
+    assert_eq!(client.get_blocks_tip_height().await.unwrap(), 42);
+    assert_eq!(transport.requests()[1].url, "https://fallback.test/api/blocks/tip/height");
+}
+#[test]
+fn transport_builder_invalid_url() {
+    for url in &["", "esplora.test/api", "https://", "https:///api", "https://esplora.test/api?x=1"] {
+        let res = ApiClient::builder().url(*url).build();
+        assert!(matches!(res, Err(Error::InvalidInput(_))), "{}", url);
+    }
+    assert!(matches!(ApiClient::builder().build(), Err(Error::InvalidInput(_))));
+    let failover = Failover {
+        fallback_urls: vec!["not a url".to_string()],
+        ..Default::default()
+    };
+    let res = ApiClient::builder().url(ENDPOINT_URL).failover(failover.clone()).build();
+    assert!(matches!(res, Err(Error::InvalidInput(_))));
+    let res = ApiClient::new(ENDPOINT_URL, None).unwrap().with_failover(failover);
+    assert!(matches!(res, Err(Error::InvalidInput(_))));
+    let res = ApiClient::with_transport("esplora.test/api", StubTransport::new(200, "42"));
+    assert!(matches!(res, Err(Error::InvalidInput(_))));
+    let client = ApiClient::with_transport("https://esplora.test/api/", StubTransport::new(200, "42")).unwrap();
+    assert_eq!(client.url, "https://esplora.test/api");
+}
+#[test]
+fn transport_builder_rejects_http_options_with_transport() {
+    let builder = || ApiClient::builder().url(ENDPOINT_URL).transport(StubTransport::new(200, "42"));
+    match builder().authorization("secret").proxy("http://proxy.test:3128").build() {
+        Err(Error::InvalidInput(message)) => assert!(message.starts_with("authorization, proxy cannot be applied")),
+        res => panic!("unexpected result {:?}", res),
+    }
+    let res = builder().options(ClientOptions::new().header("x-api-key", "key")).build();
+    assert!(matches!(res, Err(Error::InvalidInput(_))));
+    assert!(builder().timeout(Duration::from_secs(5)).retry(fast_retry()).build().is_ok());
+}
+#[test]
+fn transport_network_presets() {
+    let client = ApiClient::mempool_space(Network::Signet).unwrap();
+    assert_eq!(client.url, "https://mempool.space/signet/api");
+    assert_eq!(client.network(), Some(Network::Signet));
+    let client = ApiClient::blockstream(Network::Liquid).unwrap();
+    assert_eq!(client.url, "https://blockstream.info/liquid/api");
+    assert!(matches!(ApiClient::blockstream(Network::Testnet4), Err(Error::InvalidInput(_))));
+    assert!(matches!(ApiClient::mempool_space(Network::Regtest), Err(Error::InvalidInput(_))));
+    let client = ApiClient::builder()
+        .url("http://localhost:3002")
+        .network(Network::Regtest)
+        .build()
+        .unwrap();
+    assert_eq!(client.network(), Some(Network::Regtest));
+    assert_eq!(ApiClient::new(ENDPOINT_URL, None).unwrap().network(), None);
+}
+#[test]
+fn transport_network_names() {
+    for network in Network::ALL.iter() {
+        assert_eq!(network.to_string().parse::<Network>().unwrap(), *network);
+    }
+    assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Bitcoin);
+    assert!("litecoin".parse::<Network>().is_err());
+}
+
+/// Page of transactions built from the tx fixture, with the given txids and confirmation heights.
+fn txs_page(txs: &[(&str, Option<u32>)]) -> Response {
+    let fixture: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx.json")).unwrap();
+    let page: Vec<serde_json::Value> = txs
+        .iter()
+        .map(|(txid, height)| {
+            let mut tx = fixture.clone();
+            tx["txid"] = (*txid).into();
+            tx["status"] = match height {
+                Some(height) => serde_json::json!({"confirmed": true, "block_height": height}),
+                None => serde_json::json!({"confirmed": false}),
+            };
+            tx
+        })
+        .collect();
+    Response::new(200, serde_json::to_string(&page).unwrap())
+}
+
+fn history_transport() -> StubTransport {
+    StubTransport::sequence(vec![
+        txs_page(&[("m1", None)]),
+        txs_page(&[("c3", Some(300)), ("c2", Some(200))]),
+        txs_page(&[("c1", Some(100))]),
+        txs_page(&[]),
+    ])
+}
+#[tokio::test]
+async fn transport_address_history() {
+    use futures::TryStreamExt;
+
+    let transport = history_transport();
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let txs: Vec<_> = client.address_history("addr").try_collect().await.unwrap();
+    let txids: Vec<&str> = txs.iter().map(|tx| tx.txid.as_str()).collect();
+    assert_eq!(txids, vec!["m1", "c3", "c2", "c1"]);
+    let urls: Vec<String> = transport.requests().into_iter().map(|request| request.url).collect();
+    assert_eq!(
+        urls,
+        vec![
+            "https://esplora.test/api/address/addr/txs/mempool",
+            "https://esplora.test/api/address/addr/txs/chain",
+            "https://esplora.test/api/address/addr/txs/chain/c2",
+            "https://esplora.test/api/address/addr/txs/chain/c1",
+        ]
+    );
+}
+#[tokio::test]
+async fn transport_address_history_stop_conditions() {
+    use futures::TryStreamExt;
+
+    let transport = history_transport();
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let txs: Vec<_> = client.address_history("addr").stop_at_txid("c2").try_collect().await.unwrap();
+    let txids: Vec<&str> = txs.iter().map(|tx| tx.txid.as_str()).collect();
+    assert_eq!(txids, vec!["m1", "c3"]);
+    assert_eq!(transport.requests().len(), 2);
+
+    let client = ApiClient::with_transport(ENDPOINT_URL, history_transport()).unwrap();
+    let txs: Vec<_> = client.address_history("addr").min_height(150).try_collect().await.unwrap();
+    let txids: Vec<&str> = txs.iter().map(|tx| tx.txid.as_str()).collect();
+    assert_eq!(txids, vec!["m1", "c3", "c2"]);
+}
+#[test]
+#[cfg(feature = "blocking")]
+fn transport_blocking_script_hash_history() {
+    let transport = StubTransport::sequence(vec![
+        txs_page(&[("c2", Some(200))]),
+        Response::new(500, "Internal Server Error"),
+    ]);
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let txs: Vec<_> = client.script_hash_history("hash").skip_mempool().collect();
+    assert_eq!(txs.len(), 2);
+    assert_eq!(txs[0].as_ref().unwrap().txid, "c2");
+    assert!(matches!(txs[1], Err(Error::HttpStatus { status: 500, .. })));
+    assert_eq!(transport.requests()[1].url, "https://esplora.test/api/scripthash/hash/txs/chain/c2");
+}
+
+/// Transport answering requests by path, the last route added for a path winning, 404 for unknown paths.
+#[derive(Clone, Default)]
+struct PathTransport {
+    routes: Arc<Mutex<Vec<(String, Response)>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl PathTransport {
+    fn route(self, path: &str, response: Response) -> Self {
+        self.routes.lock().unwrap().push((format!("{}{}", ENDPOINT_URL, path), response));
+        self
+    }
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+    fn respond(&self, request: &Request) -> Response {
+        self.requests.lock().unwrap().push(request.url.clone());
+        let routes = self.routes.lock().unwrap();
+        match routes.iter().rev().find(|(url, _)| *url == request.url) {
+            Some((_, response)) => response.clone(),
+            None => Response::new(404, "Not Found"),
+        }
+    }
+}
+
+impl EsploraTransport for PathTransport {
+    fn send<'a>(&'a self, request: &'a Request) -> TransportFuture<'a> {
+        let response = self.respond(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl esplora_api::blocking::EsploraTransport for PathTransport {
+    fn send(&self, request: &Request) -> Result<Response, Error> {
+        Ok(self.respond(request))
+    }
+}
+
+/// Block of 60 transactions spread over 3 pages, the last one missing a transaction when truncated.
+fn block_pages_transport(truncated: bool) -> PathTransport {
+    let mut block: serde_json::Value = serde_json::from_str(include_str!("fixtures/block.json")).unwrap();
+    block["tx_count"] = 60.into();
+    let txids: Vec<String> = (0..60).map(|index| format!("tx{}", index)).collect();
+    let page = |start: usize, end: usize| {
+        let txs: Vec<(&str, Option<u32>)> = txids[start..end].iter().map(|txid| (txid.as_str(), Some(1))).collect();
+        txs_page(&txs)
+    };
+    PathTransport::default()
+        .route("/block/hash", Response::new(200, block.to_string()))
+        .route("/block/hash/txs/0", page(0, 25))
+        .route("/block/hash/txs/25", page(25, 50))
+        .route("/block/hash/txs/50", page(50, if truncated { 59 } else { 60 }))
+}
+#[tokio::test]
+async fn transport_block_transactions() {
+    use futures::{StreamExt, TryStreamExt};
+
+    let client = ApiClient::with_transport(ENDPOINT_URL, block_pages_transport(false)).unwrap();
+    for concurrency in [1, 2, 5].iter() {
+        let txs: Vec<_> = client.block_transactions("hash").concurrency(*concurrency).try_collect().await.unwrap();
+        let txids: Vec<String> = txs.into_iter().map(|tx| tx.txid).collect();
+        assert_eq!(txids, (0..60).map(|index| format!("tx{}", index)).collect::<Vec<_>>());
+    }
+
+    let client = ApiClient::with_transport(ENDPOINT_URL, block_pages_transport(true)).unwrap();
+    let results: Vec<_> = client.block_transactions("hash").concurrency(3).collect().await;
+    assert_eq!(results.len(), 51);
+    assert!(matches!(results[50], Err(Error::Inconsistent(_))));
+}
+#[test]
+#[cfg(feature = "blocking")]
+fn transport_blocking_block_transactions() {
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, block_pages_transport(false)).unwrap();
+    for concurrency in [1, 2, 5].iter() {
+        let txids: Vec<String> = client
+            .block_transactions("hash")
+            .concurrency(*concurrency)
+            .map(|tx| tx.unwrap().txid)
+            .collect();
+        assert_eq!(txids, (0..60).map(|index| format!("tx{}", index)).collect::<Vec<_>>());
+    }
+
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, block_pages_transport(true)).unwrap();
+    let results: Vec<_> = client.block_transactions("hash").concurrency(3).collect();
+    assert_eq!(results.len(), 51);
+    assert!(results[..50].iter().all(|result| result.is_ok()));
+    assert!(matches!(results[50], Err(Error::Inconsistent(_))));
+}
+
+/// Chain of blocks b0 to b19 served 10 per page, with block at broken_at not linking to its parent.
+fn chain_transport(tip: u32, broken_at: Option<u32>) -> PathTransport {
+    let fixture: serde_json::Value = serde_json::from_str(include_str!("fixtures/block.json")).unwrap();
+    let block = |height: u32| {
+        let mut block = fixture.clone();
+        block["id"] = format!("b{}", height).into();
+        block["height"] = height.into();
+        block["previousblockhash"] = match height {
+            0 => serde_json::Value::Null,
+            _ if Some(height) == broken_at => "orphan".into(),
+            _ => format!("b{}", height - 1).into(),
+        };
+        block
+    };
+    (0..20u32)
+        .fold(PathTransport::default(), |transport, start| {
+            let page: Vec<serde_json::Value> = (start.saturating_sub(9)..=start).rev().map(block).collect();
+            transport.route(&format!("/blocks/{}", start), Response::new(200, serde_json::to_string(&page).unwrap()))
+        })
+        .route("/blocks/tip/height", Response::new(200, tip.to_string()))
+}
+#[tokio::test]
+async fn transport_blocks_range() {
+    use futures::{StreamExt, TryStreamExt};
+
+    let transport = chain_transport(19, None);
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let blocks: Vec<_> = client.blocks_range(3..=14).try_collect().await.unwrap();
+    let heights: Vec<u32> = blocks.iter().map(|block| block.height).collect();
+    assert_eq!(heights, (3..=14).collect::<Vec<_>>());
+    assert_eq!(
+        transport.requests(),
+        vec![
+            "https://esplora.test/api/blocks/tip/height",
+            "https://esplora.test/api/blocks/12",
+            "https://esplora.test/api/blocks/14",
+        ]
+    );
+
+    // the status of the block left behind is checked, so the caches drop it if it was orphaned
+    let transport = chain_transport(19, Some(8));
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let results: Vec<_> = client.blocks_range(5..).collect().await;
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[3], Err(Error::Inconsistent(_))));
+    assert_eq!(transport.requests().last().unwrap(), "https://esplora.test/api/block/b7/status");
+}
+#[tokio::test]
+async fn transport_blocks_range_follows_tip() {
+    use futures::TryStreamExt;
+
+    let transport = chain_transport(11, None);
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    let mut blocks = client.blocks_range(10..).poll_interval(Duration::from_millis(1));
+    assert_eq!(blocks.try_next().await.unwrap().unwrap().height, 10);
+    assert_eq!(blocks.try_next().await.unwrap().unwrap().height, 11);
+    transport.clone().route("/blocks/tip/height", Response::new(200, "13"));
+    assert_eq!(blocks.try_next().await.unwrap().unwrap().height, 12);
+    assert_eq!(blocks.try_next().await.unwrap().unwrap().height, 13);
+}
+#[test]
+#[cfg(feature = "blocking")]
+fn transport_blocking_blocks_range() {
+    let client = esplora_api::blocking::ApiClient::with_transport(ENDPOINT_URL, chain_transport(19, None)).unwrap();
+    let heights: Vec<u32> = client.blocks_range(..5).map(|block| block.unwrap().height).collect();
+    assert_eq!(heights, vec![0, 1, 2, 3, 4]);
+    assert_eq!(client.blocks_range(5..5).count(), 0);
+}
+
+/// Transactions tx0 to tx19 served by path, tx7 missing.
+fn txs_transport() -> PathTransport {
+    let fixture: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx.json")).unwrap();
+    (0..20).filter(|index| *index != 7).fold(PathTransport::default(), |transport, index| {
+        let mut tx = fixture.clone();
+        tx["txid"] = format!("tx{}", index).into();
+        transport.route(&format!("/tx/tx{}", index), Response::new(200, tx.to_string()))
+    })
+}
+#[tokio::test]
+async fn transport_get_txs() {
+    let transport = txs_transport();
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_batch_concurrency(3);
+    let txids: Vec<String> = (0..20).map(|index| format!("tx{}", index)).collect();
+    let txs = client.get_txs(&txids).await;
+    assert_eq!(txs.len(), 20);
+    for (index, tx) in txs.iter().enumerate() {
+        match tx {
+            Ok(tx) => assert_eq!(tx.txid, txids[index]),
+            Err(err) => assert_eq!((index, err.status()), (7, Some(404))),
+        }
+    }
+    assert_eq!(transport.requests().len(), 20);
+    assert!(client.get_tx_statuses::<&str>(&[]).await.is_empty());
+}
+#[test]
+#[cfg(feature = "blocking")]
+fn transport_blocking_get_txs() {
+    let client = esplora_api::blocking::ApiClient::builder()
+        .url(ENDPOINT_URL)
+        .transport(txs_transport())
+        .batch_concurrency(4)
+        .build()
+        .unwrap();
+    let txids: Vec<String> = (0..20).map(|index| format!("tx{}", index)).collect();
+    let txs = client.get_txs(&txids);
+    assert_eq!(txs.len(), 20);
+    for (index, tx) in txs.iter().enumerate() {
+        match tx {
+            Ok(tx) => assert_eq!(tx.txid, txids[index]),
+            Err(err) => assert_eq!((index, err.status()), (7, Some(404))),
+        }
+    }
+}
+
+/// Transport answering `42`, or a transport error when failing, after a short delay, counting the requests it received.
+#[derive(Clone, Default)]
+struct SlowTransport {
+    calls: Arc<Mutex<usize>>,
+    failing: bool,
+}
+
+impl EsploraTransport for SlowTransport {
+    fn send<'a>(&'a self, _request: &'a Request) -> TransportFuture<'a> {
+        *self.calls.lock().unwrap() += 1;
+        let failing = self.failing;
+        Box::pin(async move {
+            tokio::time::delay_for(Duration::from_millis(20)).await;
+            if failing {
+                Err(Error::Transport("connection reset".into()))
+            } else {
+                Ok(Response::new(200, "42"))
+            }
+        })
+    }
+}
+#[tokio::test]
+async fn transport_single_flight() {
+    let transport = SlowTransport::default();
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_single_flight();
+    let heights = futures::future::join_all((0..5).map(|_| client.get_blocks_tip_height())).await;
+    assert!(heights.into_iter().all(|height| height.unwrap() == 42));
+    assert_eq!(*transport.calls.lock().unwrap(), 1);
+
+    // completed flights are not reused, distinct requests are not merged
+    let (height, hash) = futures::join!(client.get_blocks_tip_height(), client.get_blocks_tip_hash());
+    assert_eq!((height.unwrap(), hash.unwrap()), (42, "42".to_string()));
+    assert_eq!(*transport.calls.lock().unwrap(), 3);
+
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap();
+    futures::future::join_all((0..5).map(|_| client.get_blocks_tip_height())).await;
+    assert_eq!(*transport.calls.lock().unwrap(), 8);
+
+    // enabled from the builder or the options
+    let builder = || ApiClient::builder().url(ENDPOINT_URL).transport(transport.clone());
+    for client in [
+        builder().single_flight(true).build().unwrap(),
+        builder().options(ClientOptions::new().single_flight(true)).build().unwrap(),
+    ] {
+        futures::future::join_all((0..5).map(|_| client.get_blocks_tip_height())).await;
+    }
+    assert_eq!(*transport.calls.lock().unwrap(), 10);
+}
+#[tokio::test]
+async fn transport_single_flight_shares_errors() {
+    let transport = SlowTransport {
+        failing: true,
+        ..Default::default()
+    };
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_single_flight();
+    let heights = futures::future::join_all((0..3).map(|_| client.get_blocks_tip_height())).await;
+    for height in heights {
+        match height {
+            Err(Error::Transport(err)) => assert_eq!(err.to_string(), "connection reset"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert_eq!(*transport.calls.lock().unwrap(), 1);
+}
+#[tokio::test]
+async fn transport_cache() {
+    let transport = PathTransport::default()
+        .route("/block/hash", Response::new(200, include_str!("fixtures/block.json")))
+        .route("/tx/confirmed", Response::new(200, include_str!("fixtures/tx.json")))
+        .route("/tx/unconfirmed", Response::new(200, include_str!("fixtures/tx_segwit_unconfirmed.json")))
+        .route("/blocks/tip/height", Response::new(200, "42"))
+        .route("/tx/confirmed/merkle-proof", Response::new(200, include_str!("fixtures/merkle_proof.json")))
+        .route(&format!("/block/{}/status", TX_BLOCK), Response::new(200, r#"{"in_best_chain":true}"#));
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_cache(CachePolicy {
+        capacity: 10,
+        ttl: Duration::from_secs(0),
+    });
+    for _ in 0..2 {
+        client.get_block("hash").await.unwrap();
+        client.get_tx("confirmed").await.unwrap();
+        client.get_tx("unconfirmed").await.unwrap();
+        client.get_blocks_tip_height().await.unwrap();
+        client.get_tx("missing").await.unwrap_err();
+        client.get_tx_status("confirmed").await.unwrap_err();
+        client.get_tx_merkle_proof("confirmed").await.unwrap();
+    }
+    // a cached confirmed transaction is served once its block is still in the best chain
+    let status = format!("/block/{}/status", TX_BLOCK);
+    let paths: Vec<String> = transport.requests().into_iter().map(|url| url.replace(ENDPOINT_URL, "")).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/block/hash",
+            "/tx/confirmed",
+            "/tx/unconfirmed",
+            "/blocks/tip/height",
+            "/tx/missing",
+            "/tx/confirmed/status",
+            "/tx/confirmed/merkle-proof",
+            status.as_str(),
+            "/tx/unconfirmed",
+            "/blocks/tip/height",
+            "/tx/missing",
+            "/tx/confirmed/status",
+            "/tx/confirmed/merkle-proof",
+        ]
+    );
+    assert_eq!(
+        client.clone().cache_stats(),
+        Some(CacheStats {
+            hits: 2,
+            misses: 8,
+            entries: 3,
+        })
+    );
+    assert_eq!(ApiClient::with_transport(ENDPOINT_URL, transport).unwrap().cache_stats(), None);
+}
+#[tokio::test]
+async fn transport_cache_evicts_least_recently_used() {
+    let transport = txs_transport().route(&format!("/block/{}/status", TX_BLOCK), Response::new(200, r#"{"in_best_chain":true}"#));
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_cache(CachePolicy {
+        capacity: 2,
+        ..Default::default()
+    });
+    for txid in ["tx0", "tx1", "tx0", "tx2", "tx0", "tx1"].iter() {
+        client.get_tx(txid).await.unwrap();
+    }
+    assert_eq!(transport.requests().iter().filter(|url| url.contains("/tx/")).count(), 4);
+    assert_eq!(client.cache_stats().unwrap().entries, 2);
+}
+#[tokio::test]
+async fn transport_persistent_cache() {
+    let dir = std::env::temp_dir().join(format!("esplora-api-store-{}", std::process::id()));
+    let status = format!("/block/{}/status", TX_BLOCK);
+    let online = PathTransport::default()
+        .route("/block/hash", Response::new(200, include_str!("fixtures/block.json")))
+        .route("/tx/confirmed", Response::new(200, include_str!("fixtures/tx.json")))
+        .route("/tx/unconfirmed", Response::new(200, include_str!("fixtures/tx_segwit_unconfirmed.json")));
+    let client = ApiClient::with_transport(ENDPOINT_URL, online).unwrap().with_persistent_cache(FsStore::new(&dir).unwrap());
+    client.get_block("hash").await.unwrap();
+    client.get_tx("confirmed").await.unwrap();
+    client.get_tx("unconfirmed").await.unwrap();
+
+    // a new client reads the store left by the previous one
+    let offline = PathTransport::default().route(&status, Response::new(200, r#"{"in_best_chain":true}"#));
+    let client = ApiClient::with_transport(ENDPOINT_URL, offline.clone()).unwrap().with_persistent_cache(FsStore::new(&dir).unwrap());
+    assert_eq!(client.get_block("hash").await.unwrap().height, 100000);
+    assert_eq!(client.get_tx("confirmed").await.unwrap().fee, 0);
+    assert_eq!(client.get_tx("unconfirmed").await.unwrap_err().status(), Some(404));
+
+    // the block confirming the transaction left the best chain
+    offline.route(&status, Response::new(200, r#"{"in_best_chain":false}"#));
+    assert!(!client.get_block_status(TX_BLOCK).await.unwrap().in_best_chain);
+    assert_eq!(client.get_tx("confirmed").await.unwrap_err().status(), Some(404));
+    assert_eq!(client.get_block("hash").await.unwrap().height, 100000);
+    client.invalidate_block("hash").unwrap();
+    assert_eq!(client.get_block("hash").await.unwrap_err().status(), Some(404));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+#[tokio::test]
+async fn transport_caches_reorg() {
+    let dir = std::env::temp_dir().join(format!("esplora-api-reorg-{}", std::process::id()));
+    let status = format!("/block/{}/status", TX_BLOCK);
+    let mut unconfirmed: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx.json")).unwrap();
+    unconfirmed["status"] = serde_json::json!({ "confirmed": false });
+    let transport = PathTransport::default()
+        .route("/block/hash", Response::new(200, include_str!("fixtures/block.json")))
+        .route("/block/hash/status", Response::new(200, r#"{"in_best_chain":true}"#))
+        .route("/tx/confirmed", Response::new(200, include_str!("fixtures/tx.json")))
+        .route(&status, Response::new(200, r#"{"in_best_chain":true}"#));
+    let client = ApiClient::builder()
+        .url(ENDPOINT_URL)
+        .transport(transport.clone())
+        .cache(CachePolicy {
+            ttl: Duration::from_millis(100),
+            ..Default::default()
+        })
+        .persistent_cache(FsStore::new(&dir).unwrap())
+        .build()
+        .unwrap();
+    client.get_block("hash").await.unwrap();
+    // the block status is checked once per ttl
+    for _ in 0..4 {
+        assert!(client.get_tx("confirmed").await.unwrap().status.unwrap().confirmed);
+    }
+    assert_eq!(client.cache_stats().unwrap(), CacheStats { hits: 3, misses: 2, entries: 2 });
+
+    // the transaction goes back to the mempool: its block status is checked again before serving it from the caches
+    tokio::time::delay_for(Duration::from_millis(150)).await;
+    transport
+        .clone()
+        .route(&status, Response::new(200, r#"{"in_best_chain":false}"#))
+        .route("/tx/confirmed", Response::new(200, unconfirmed.to_string()));
+    assert!(!client.get_tx("confirmed").await.unwrap().status.unwrap().confirmed);
+    assert_eq!(client.cache_stats().unwrap(), CacheStats { hits: 3, misses: 3, entries: 1 });
+
+    // a block status out of the best chain drops the block from both caches
+    transport.clone().route("/block/hash/status", Response::new(200, r#"{"in_best_chain":false}"#));
+    assert!(!client.get_block_status("hash").await.unwrap().in_best_chain);
+    assert_eq!(client.cache_stats().unwrap().entries, 0);
+    let paths: Vec<String> = transport.requests().into_iter().map(|url| url.replace(ENDPOINT_URL, "")).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/block/hash",
+            "/tx/confirmed",
+            status.as_str(),
+            status.as_str(),
+            "/tx/confirmed",
+            "/block/hash/status",
+        ]
+    );
+    let offline = ApiClient::with_transport(ENDPOINT_URL, PathTransport::default())
+        .unwrap()
+        .with_persistent_cache(FsStore::new(&dir).unwrap());
+    assert_eq!(offline.get_block("hash").await.unwrap_err().status(), Some(404));
+    assert_eq!(offline.get_tx("confirmed").await.unwrap_err().status(), Some(404));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+#[tokio::test]
+#[cfg(feature = "sled")]
+async fn transport_persistent_cache_sled() {
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    let store = esplora_api::SledStore::new(db.open_tree("test").unwrap());
+    let transport = PathTransport::default().route("/block/hash/raw", Response::new(200, vec![1, 2, 3]));
+    let client = ApiClient::with_transport(ENDPOINT_URL, transport.clone()).unwrap().with_persistent_cache(store.clone());
+    client.get_block_raw_format("hash").await.unwrap();
+    let client = ApiClient::builder()
+        .url(ENDPOINT_URL)
+        .transport(transport.clone())
+        .persistent_cache(store)
+        .build()
+        .unwrap();
+    assert_eq!(client.get_block_raw_format("hash").await.unwrap(), vec![1, 2, 3]);
+    assert_eq!(transport.requests().len(), 1);
+}